@@ -0,0 +1,57 @@
+// Benchmark: Random playouts on GameState and Bitboard (the two are cross-checked in tests/board_crosscheck.rs)
+// Run with: cargo bench --bench playouts
+use std::hint::black_box;
+use std::time::Instant;
+
+use go_game::{Bitboard, Board, GameState, Rng, Ruleset};
+
+// Playout: Play random moves until the game ends (or a move limit), returning the moves played
+fn playout(board: &mut impl Board, seed: u64) -> usize {
+    let mut rng = Rng::new(seed);
+    let limit = board.size() * board.size() * 3;
+    let mut empty = Vec::with_capacity(board.size() * board.size());
+    for moves in 0..limit {
+        if !board.play_random_move(&mut rng, &mut empty) {
+            return moves;
+        }
+    }
+    limit
+}
+
+// Time: Playouts per second from an empty board
+fn rate(mut board: impl FnMut() -> usize, playouts: u64) -> f64 {
+    let start = Instant::now();
+    for _ in 0..playouts {
+        black_box(board());
+    }
+    playouts as f64 / start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    for (size, playouts) in [(9, 2000), (13, 500), (19, 200)] {
+        let mut seed = 0;
+        let game = rate(
+            || {
+                seed += 1;
+                playout(&mut GameState::with_rules(size, Ruleset::Chinese), seed)
+            },
+            playouts,
+        );
+        let mut seed = 0;
+        let bitboard = rate(
+            || {
+                seed += 1;
+                playout(&mut Bitboard::new(size, Ruleset::Chinese), seed)
+            },
+            playouts,
+        );
+        println!(
+            "{}x{}: GameState {:>8.1} playouts/s   Bitboard {:>8.1} playouts/s   ({:.1}x)",
+            size,
+            size,
+            game,
+            bitboard,
+            bitboard / game
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// Benchmark: Legal move generation on a 19x19 middle-game position, against a flood-fill reference
+// Run with: cargo bench --bench valid_moves
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::hint::black_box;
+use std::time::Instant;
+
+use go_game::{GameState, Move, Point, Ruleset, Stone};
+
+// Position: Play pseudo-random legal moves (fixed seed) to reach a crowded middle game
+// Also returns a hash of every position reached, for the reference superko check
+fn middle_game(moves: usize) -> (GameState, HashSet<u64>) {
+    let mut game = GameState::with_rules(19, Ruleset::Chinese);
+    let mut seen = HashSet::from([board_hash(game.board())]);
+    let mut rng: u64 = 0x9E37_79B9_7F4A_7C15;
+    for _ in 0..moves {
+        let valid = game.valid_moves();
+        if valid.is_empty() {
+            break;
+        }
+        rng ^= rng << 13;
+        rng ^= rng >> 7;
+        rng ^= rng << 17;
+        let point = valid[(rng % valid.len() as u64) as usize];
+        if game.try_play(Move::Play(point)).is_ok() {
+            seen.insert(board_hash(game.board()));
+        }
+    }
+    (game, seen)
+}
+
+// Hash: Whole-board hash, recomputed for every candidate as the flood-fill rules did
+fn board_hash(board: &[Vec<Stone>]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    board.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Neighbors: Adjacent points on the board
+fn neighbors(size: usize, (row, col): Point) -> impl Iterator<Item = Point> {
+    [(row.wrapping_sub(1), col), (row + 1, col), (row, col.wrapping_sub(1)), (row, col + 1)]
+        .into_iter()
+        .filter(move |&(r, c)| r < size && c < size)
+}
+
+// Group: Flood-fill the chain at point, returning its stones and whether it has a liberty
+fn flood_group(board: &[Vec<Stone>], point: Point) -> (HashSet<Point>, bool) {
+    let size = board.len();
+    let stone = board[point.0][point.1];
+    let mut group = HashSet::from([point]);
+    let mut queue = VecDeque::from([point]);
+    let mut free = false;
+    while let Some(point) = queue.pop_front() {
+        for (r, c) in neighbors(size, point) {
+            match board[r][c] {
+                Stone::Empty => free = true,
+                s if s == stone && group.insert((r, c)) => queue.push_back((r, c)),
+                _ => {}
+            }
+        }
+    }
+    (group, free)
+}
+
+// Reference: Legal moves the way they were found before chains were tracked - copy the board for every
+// empty point, flood-fill neighbors for captures and the new chain for suicide, then hash for superko
+// (Chinese rules: positional superko, no suicide)
+fn flood_fill_valid_moves(game: &GameState, seen: &HashSet<u64>) -> Vec<Point> {
+    let size = game.size();
+    let player = game.current_player();
+    let mut valid = Vec::new();
+    for row in 0..size {
+        for col in 0..size {
+            if game.board()[row][col] != Stone::Empty {
+                continue;
+            }
+            let mut board = game.board().to_vec();
+            board[row][col] = player;
+            let mut captured = false;
+            for (r, c) in neighbors(size, (row, col)) {
+                if board[r][c] == Stone::Empty || board[r][c] == player {
+                    continue;
+                }
+                let (group, free) = flood_group(&board, (r, c));
+                if !free {
+                    group.iter().for_each(|&(r, c)| board[r][c] = Stone::Empty);
+                    captured = true;
+                }
+            }
+            if !captured && !flood_group(&board, (row, col)).1 {
+                continue;
+            }
+            if !seen.contains(&board_hash(&board)) {
+                valid.push((row, col));
+            }
+        }
+    }
+    valid
+}
+
+// Time: Average microseconds per call of f over the given number of iterations
+fn time<T>(iterations: u32, mut f: impl FnMut() -> T) -> f64 {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    start.elapsed().as_secs_f64() * 1e6 / iterations as f64
+}
+
+fn main() {
+    for moves in [0, 100, 200] {
+        let (game, seen) = middle_game(moves);
+        assert_eq!(flood_fill_valid_moves(&game, &seen), game.valid_moves(), "reference disagrees");
+        let valid = time(200, || game.valid_moves());
+        let flood = time(200, || flood_fill_valid_moves(&game, &seen));
+        let view = time(200, || game.board_view());
+        println!(
+            "{:>3} moves: valid_moves {:>9.1} us   flood fill {:>9.1} us ({:.1}x)   board_view {:>9.1} us   ({} legal)",
+            moves,
+            valid,
+            flood,
+            flood / valid,
+            view,
+            game.valid_moves().len()
+        );
+    }
+}
@@ -1,9 +1,30 @@
 // Import: WebAssembly module and game classes
-import init, { GameState, Stone } from './pkg/go_game.js';
+import init, { GameState, Stone, Ruleset, HandicapPlacement, GamePhase, ResultReason, TimeControl, TimeSystem, Bot, Difficulty } from './pkg/go_game.js';
 
 // Global: Current game state instance
 let game = null;
 
+// Global: Time system of the current game's clock (null for an untimed game)
+let timeSystem = null;
+
+// Global: Computer opponent - the bot and the color it plays (null for two players)
+let bot = null;
+let computerColor = null;
+
+// Global: Ownership estimate shown over the board (null when hidden)
+let showEstimate = false;
+let estimate = null;
+
+// Global: Influence map (territory, moyo and contested points) shown over the board (null when hidden)
+let showInfluence = false;
+let influence = null;
+
+// Global: Point whose move warnings were shown - clicking it again plays anyway (null when none pending)
+let warnedPoint = null;
+
+// Constant: Random playouts per ownership estimate
+const ESTIMATE_PLAYOUTS = 100;
+
 // Init: Initialize WASM, create game, render board, and set up event listeners
 async function run() {
     await init();
@@ -12,12 +33,15 @@ async function run() {
     updateUI();
     setupEventListeners();
     
+    // Clock: Refresh clocks and watch for flag fall
+    setInterval(updateClocks, 200);
+    
     // Export: Make test function available globally for console access
     window.testGuanzi = testGuanzi;
     console.log('testGuanzi() is now available in the console');
 }
 
-// Events: Set up click handlers for Pass and Reset buttons
+// Events: Set up click handlers for Undo, Redo, Pass, Reset and SGF buttons
 function setupEventListeners() {
     document.getElementById('pass-btn').addEventListener('click', () => {
         if (!game || (typeof game.game_over === 'function' ? game.game_over() : game.game_over)) {
@@ -26,6 +50,64 @@ function setupEventListeners() {
         game.pass();
         renderBoard();
         updateUI();
+        scheduleComputerMove();
+    });
+
+    // Resign: The player to move gives up the game
+    document.getElementById('resign-btn').addEventListener('click', () => {
+        if (game && game.resign(game.current_player)) {
+            renderBoard();
+            updateUI();
+        }
+    });
+
+    // Scoring: Both players at this board agree to the dead stones, or go back to playing
+    document.getElementById('accept-btn').addEventListener('click', () => {
+        if (game && game.accept_score(Stone.Black) && game.accept_score(Stone.White)) {
+            renderBoard();
+            updateUI();
+        }
+    });
+
+    document.getElementById('resume-btn').addEventListener('click', () => {
+        if (game && game.resume_play()) {
+            renderBoard();
+            updateUI();
+            scheduleComputerMove();
+        }
+    });
+
+    // Estimate: Show or hide who is likely to own each point, and who is ahead
+    document.getElementById('estimate-btn').addEventListener('click', () => {
+        showEstimate = !showEstimate;
+        showInfluence = showInfluence && !showEstimate;
+        updateOverlayButtons();
+        renderBoard();
+        updateUI();
+    });
+
+    // Influence: Show or hide each color's territory, moyo (frameworks) and the contested points between them
+    document.getElementById('influence-btn').addEventListener('click', () => {
+        showInfluence = !showInfluence;
+        showEstimate = showEstimate && !showInfluence;
+        updateOverlayButtons();
+        renderBoard();
+        updateUI();
+    });
+
+    // Undo/Redo: Step back and forward through the move record
+    document.getElementById('undo-btn').addEventListener('click', () => {
+        if (game && game.undo()) {
+            renderBoard();
+            updateUI();
+        }
+    });
+
+    document.getElementById('redo-btn').addEventListener('click', () => {
+        if (game && game.redo()) {
+            renderBoard();
+            updateUI();
+        }
     });
 
     document.getElementById('reset-btn').addEventListener('click', () => {
@@ -35,21 +117,178 @@ function setupEventListeners() {
         game.reset();
         renderBoard();
         updateUI();
+        scheduleComputerMove();
+    });
+
+    // SGF: Download the current game as an SGF record
+    document.getElementById('save-sgf-btn').addEventListener('click', () => {
+        if (!game) {
+            return;
+        }
+        const blob = new Blob([game.to_sgf()], { type: 'application/x-go-sgf' });
+        const link = document.createElement('a');
+        link.href = URL.createObjectURL(blob);
+        link.download = 'game.sgf';
+        link.click();
+        URL.revokeObjectURL(link.href);
+    });
+
+    // SGF: Open an SGF record chosen from disk
+    document.getElementById('load-sgf-btn').addEventListener('click', () => {
+        document.getElementById('load-sgf-input').click();
+    });
+
+    document.getElementById('load-sgf-input').addEventListener('change', async (event) => {
+        const file = event.target.files[0];
+        if (!file) {
+            return;
+        }
+        try {
+            game = GameState.from_sgf(await file.text());
+            timeSystem = null;
+            renderBoard();
+            updateUI();
+        } catch (error) {
+            document.getElementById('status').textContent = `Could not load SGF: ${error}`;
+        }
+        event.target.value = '';
     });
+
+    // Size: Start a new game on the selected board size (9x9, 13x13, 19x19)
+    document.getElementById('size-select').addEventListener('change', newGameFromSettings);
+
+    // Ruleset: Start a new game under the selected ruleset
+    document.getElementById('ruleset-select').addEventListener('change', newGameFromSettings);
+
+    // Handicap: Start a new game with fixed handicap stones
+    document.getElementById('handicap-select').addEventListener('change', newGameFromSettings);
+
+    // Clock: Start a new game with the selected time control
+    document.getElementById('time-select').addEventListener('change', newGameFromSettings);
+    document.getElementById('opponent-select').addEventListener('change', newGameFromSettings);
+    document.getElementById('difficulty-select').addEventListener('change', newGameFromSettings);
+}
+
+// Time Control: Build the selected time control (null for an untimed game)
+function timeControlFromSettings() {
+    const minutes = 60 * 1000;
+    switch (document.getElementById('time-select').value) {
+        case 'absolute': return TimeControl.absolute(10 * minutes);
+        case 'fischer': return TimeControl.fischer(5 * minutes, 10 * 1000);
+        case 'byoyomi': return TimeControl.byo_yomi(10 * minutes, 30 * 1000, 5);
+        case 'canadian': return TimeControl.canadian(10 * minutes, 5 * minutes, 25);
+        case 'simple': return TimeControl.simple(30 * 1000);
+        default: return null;
+    }
+}
+
+// New Game: Create a game from the selected board size, ruleset and handicap
+function newGameFromSettings() {
+    const size = parseInt(document.getElementById('size-select').value, 10);
+    const ruleset = Ruleset[document.getElementById('ruleset-select').value];
+    const handicap = parseInt(document.getElementById('handicap-select').value, 10);
+    game = GameState.with_rules(size, ruleset);
+    if (!game.new_handicap(handicap, HandicapPlacement.Fixed)) {
+        // Size has no standard layout for this many stones: fall back to an even game
+        document.getElementById('handicap-select').value = '0';
+    }
+    const timeControl = timeControlFromSettings();
+    timeSystem = timeControl ? timeControl.system : null;
+    if (timeControl) {
+        game.set_time_control(timeControl);
+    }
+    const opponent = document.getElementById('opponent-select').value;
+    computerColor = { white: Stone.White, black: Stone.Black }[opponent] ?? null;
+    const difficulty = Difficulty[document.getElementById('difficulty-select').value];
+    bot = computerColor === null ? null : new Bot(difficulty, Date.now() >>> 0);
+    renderBoard();
+    updateUI();
+    scheduleComputerMove();
+}
+
+// Computer: Play the bot's move after the board has been redrawn, if it is the bot's turn
+function scheduleComputerMove() {
+    if (!game || !bot || game.phase !== GamePhase.Playing || game.current_player !== computerColor) {
+        return;
+    }
+    document.getElementById('status').textContent = 'Computer is thinking...';
+    setTimeout(() => {
+        if (game.phase !== GamePhase.Playing || game.current_player !== computerColor) {
+            return;
+        }
+        try {
+            game.play_bot(bot);
+        } catch (error) {
+            document.getElementById('status').textContent = error.message;
+            return;
+        }
+        renderBoard();
+        updateUI();
+    }, 50);
+}
+
+// Clock: Show both players' time and end the game if the running side's flag has fallen
+function updateClocks() {
+    if (!game) {
+        return;
+    }
+    if (game.check_time()) {
+        renderBoard();
+        updateUI();
+    }
+    for (const [player, id] of [[Stone.Black, 'black-clock'], [Stone.White, 'white-clock']]) {
+        const el = document.getElementById(id);
+        if (timeSystem === null) {
+            el.textContent = '--:--';
+            continue;
+        }
+        const main = game.main_time_left(player);
+        let text = formatTime(main);
+        if (main <= 0 && timeSystem !== TimeSystem.Absolute && timeSystem !== TimeSystem.Fischer) {
+            text = formatTime(game.period_time_left(player));
+            if (timeSystem === TimeSystem.ByoYomi) {
+                text += ` (${game.periods_left(player)})`;
+            } else if (timeSystem === TimeSystem.Canadian) {
+                text += ` /${game.stones_left(player)}`;
+            }
+        }
+        el.textContent = text;
+    }
 }
 
-// Render: Build the 19x19 Go board with stones, labels, and intersections
+// Clock: Format milliseconds as m:ss
+function formatTime(ms) {
+    const seconds = Math.max(0, Math.ceil(ms / 1000));
+    return `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, '0')}`;
+}
+
+// Overlays: Label the estimate and influence toggles (only one overlay is shown at a time)
+function updateOverlayButtons() {
+    document.getElementById('estimate-btn').textContent = showEstimate ? 'Hide Estimate' : 'Estimate';
+    document.getElementById('influence-btn').textContent = showInfluence ? 'Hide Influence' : 'Influence';
+}
+
+// Render: Build the NxN Go board with stones, labels, and intersections
 function renderBoard() {
+    warnedPoint = null; // Warnings: A changed board needs a fresh warning
     const board = document.getElementById('game-board');
     board.innerHTML = '';
     
+    const size = game.size;
+    board.style.setProperty('--board-size', size);
     const boardData = game.get_board_data();
+    estimate = showEstimate && game.phase === GamePhase.Playing ? game.estimate(ESTIMATE_PLAYOUTS, 1) : null;
+    const ownership = estimate ? estimate.ownership : null;
+    influence = showInfluence && game.phase === GamePhase.Playing ? game.influence() : null;
+    const territoryCodes = influence ? influence.territory_codes : null;
+    const moyoCodes = influence ? influence.moyo_codes : null;
+    const contestedCodes = influence ? influence.contested_codes : null;
     
     // Labels: Add coordinate labels at the top (A-S)
     const topLabels = document.createElement('div');
     topLabels.className = 'board-labels top-labels';
     topLabels.appendChild(document.createElement('div')); // Empty corner
-    for (let col = 0; col < 19; col++) {
+    for (let col = 0; col < size; col++) {
         const label = document.createElement('div');
         label.className = 'board-label';
         label.textContent = game.get_column_label(col);
@@ -57,26 +296,34 @@ function renderBoard() {
     }
     board.appendChild(topLabels);
     
-    // Grid: Create 19x19 board grid with intersections
-    for (let row = 0; row < 19; row++) {
+    // Grid: Create NxN board grid with intersections
+    for (let row = 0; row < size; row++) {
         const boardRow = document.createElement('div');
         boardRow.className = 'board-row';
         
-        // Label: Add row label on the left (19-1)
+        // Label: Add row label on the left (N-1)
         const leftLabel = document.createElement('div');
         leftLabel.className = 'board-label row-label';
         leftLabel.textContent = game.get_row_label(row);
         boardRow.appendChild(leftLabel);
         
-        // Intersections: Create 19x19 grid intersections (clickable positions)
-        for (let col = 0; col < 19; col++) {
-            const index = row * 19 + col;
+        // Intersections: Create NxN grid intersections (clickable positions)
+        for (let col = 0; col < size; col++) {
+            const index = row * size + col;
             const data = boardData[index];
             const intersection = document.createElement('div');
             intersection.className = 'intersection';
             intersection.dataset.row = data.row;
             intersection.dataset.col = data.col;
             
+            // Edges: Mark last row/column so grid lines stop at the border
+            if (row === size - 1) {
+                intersection.className += ' edge-bottom';
+            }
+            if (col === size - 1) {
+                intersection.className += ' edge-right';
+            }
+            
             // Stone: Add black or white stone if present
             if (data.stone === 1) {
                 intersection.className += ' black-stone';
@@ -84,6 +331,42 @@ function renderBoard() {
                 intersection.className += ' white-stone';
             }
             
+            // Scoring: Fade dead stones, outline seki groups and mark territory once the game is over
+            if (data.is_dead) {
+                intersection.className += ' dead-stone';
+            }
+            if (data.is_seki) {
+                intersection.className += ' seki-stone';
+            }
+            if (data.territory !== 0) {
+                const marker = document.createElement('div');
+                marker.className = `territory-marker ${data.territory === 1 ? 'territory-black' : 'territory-white'}`;
+                intersection.appendChild(marker);
+            }
+            
+            // Estimate: Shade empty points by how surely each color owns them
+            if (ownership && data.stone === 0 && ownership[index] !== 0) {
+                const marker = document.createElement('div');
+                marker.className = `ownership-marker ${ownership[index] > 0 ? 'territory-black' : 'territory-white'}`;
+                marker.style.opacity = Math.abs(ownership[index]);
+                intersection.appendChild(marker);
+            }
+
+            // Influence: Solid shading for territory, faint for moyo, a ring for contested points
+            if (influence && data.stone === 0) {
+                const owner = territoryCodes[index] || moyoCodes[index];
+                if (owner !== 0) {
+                    const marker = document.createElement('div');
+                    marker.className = `ownership-marker ${owner === 1 ? 'territory-black' : 'territory-white'}`;
+                    marker.style.opacity = territoryCodes[index] !== 0 ? 0.8 : 0.35;
+                    intersection.appendChild(marker);
+                } else if (contestedCodes[index] === 1) {
+                    const marker = document.createElement('div');
+                    marker.className = 'contested-marker';
+                    intersection.appendChild(marker);
+                }
+            }
+            
             // Star Point: Add hoshi marker on empty intersections
             if (data.is_star_point && data.stone === 0) {
                 const starPoint = document.createElement('div');
@@ -123,7 +406,7 @@ function renderBoard() {
     const bottomLabels = document.createElement('div');
     bottomLabels.className = 'board-labels bottom-labels';
     bottomLabels.appendChild(document.createElement('div')); // Empty corner
-    for (let col = 0; col < 19; col++) {
+    for (let col = 0; col < size; col++) {
         const label = document.createElement('div');
         label.className = 'board-label';
         label.textContent = game.get_column_label(col);
@@ -156,48 +439,121 @@ function updateUI() {
     document.getElementById('black-captured').textContent = blackCaptured;
     document.getElementById('white-captured').textContent = whiteCaptured;
     
-    // Status: Show game over message with scores if both players passed
+    // Scoring: Accept/Resume are only offered while dead stones are being agreed
+    const phase = game.phase;
+    document.getElementById('accept-btn').hidden = phase !== GamePhase.Scoring;
+    document.getElementById('resume-btn').hidden = phase !== GamePhase.Scoring;
+    
+    // Status: Show score preview while marking dead stones, then the agreed result
     const statusEl = document.getElementById('status');
-    const isGameOver = typeof game.game_over === 'function' ? game.game_over() : game.game_over;
-    if (isGameOver) {
-        // Calculate final scores with komi
-        const [blackScore, whiteScore] = game.calculate_scores();
-        const winner = blackScore > whiteScore ? 'Black' : whiteScore > blackScore ? 'White' : 'Tie';
-        
+    if (phase === GamePhase.Scoring) {
+        const [blackScore, whiteScore] = game.preview_scores();
+        statusEl.style.color = '#212529';
+        statusEl.textContent = `Click groups to mark them dead. Black: ${blackScore.toFixed(1)} | White: ${whiteScore.toFixed(1)} (Komi: ${game.komi})`;
+        board.classList.remove('cursor-black', 'cursor-white');
+    } else if (phase === GamePhase.Finished) {
+        const result = game.result;
         let statusText = `Game Over! `;
-        statusText += `Black: ${blackScore.toFixed(1)} | White: ${whiteScore.toFixed(1)} (Komi: 6.5)`;
         
-        if (winner === 'Tie') {
-            statusText += ' - Tie!';
+        // Counted games show final scores with komi; other endings show the reason
+        if (result.reason === ResultReason.Score || result.reason === ResultReason.Draw) {
+            const [blackScore, whiteScore] = game.calculate_scores();
+            statusText += `Black: ${blackScore.toFixed(1)} | White: ${whiteScore.toFixed(1)} (Komi: ${game.komi})`;
+        } else if (result.reason === ResultReason.Resign) {
+            statusText += 'Resignation';
+        } else if (result.reason === ResultReason.Time) {
+            statusText += 'Time';
+        } else if (result.reason === ResultReason.Forfeit) {
+            statusText += 'Forfeit';
         } else {
-            statusText += ` - ${winner} wins!`;
+            statusText += 'No result';
+        }
+        
+        if (result.winner === Stone.Black) {
+            statusText += ` - Black wins! (${result.toString()})`;
+        } else if (result.winner === Stone.White) {
+            statusText += ` - White wins! (${result.toString()})`;
+        } else if (result.reason === ResultReason.Draw) {
+            statusText += ' - Tie!';
         }
         statusEl.style.color = '#212529'; // Black text for better readability
         
         statusEl.textContent = statusText;
         // Remove cursor classes when game is over
         board.classList.remove('cursor-black', 'cursor-white');
+    } else if (estimate) {
+        const lead = estimate.lead;
+        statusEl.style.color = '#212529';
+        statusEl.textContent = `Estimate: ${lead >= 0 ? 'Black' : 'White'} leads by ${Math.abs(lead).toFixed(1)}`;
     } else {
         statusEl.textContent = '';
     }
 }
 
+// Warnings: Sentence for the risks of a candidate move ('' when it has none or is illegal)
+function describeWarnings(warnings) {
+    if (!warnings) {
+        return '';
+    }
+    const messages = [];
+    if (warnings.allows_snapback) {
+        messages.push('This capture allows a snapback.');
+    } else if (warnings.self_atari) {
+        messages.push('This move puts your own stones in atari.');
+    }
+    if (warnings.fills_own_eye) {
+        messages.push('This move fills your own eye.');
+    }
+    if (warnings.ignores_atari) {
+        messages.push('One of your groups is in atari.');
+    }
+    return messages.join(' ');
+}
+
 // Click: Handle stone placement when intersection is clicked
 function handleIntersectionClick(row, col) {
     if (!game) {
         return;
     }
+    // Scoring: Clicks toggle dead groups instead of placing stones
+    if (game.phase === GamePhase.Scoring) {
+        if (game.toggle_dead_group(row, col)) {
+            renderBoard();
+            updateUI();
+        }
+        return;
+    }
     const isGameOver = typeof game.game_over === 'function' ? game.game_over() : game.game_over;
     if (isGameOver) {
         return;
     }
     
-    const success = game.place_stone(row, col);
-    
-    if (success) {
-        renderBoard();
-        updateUI();
+    // Play: Illegal moves throw { kind, message }, shown in the status line
+    if (bot && game.current_player === computerColor) {
+        return;
     }
+
+    // Warnings: Describe a risky move first; a second click on the same point confirms it
+    const warning = describeWarnings(game.move_warnings(row, col));
+    const confirmed = warnedPoint && warnedPoint[0] === row && warnedPoint[1] === col;
+    if (warning && !confirmed) {
+        warnedPoint = [row, col];
+        const statusEl = document.getElementById('status');
+        statusEl.style.color = '#c0392b';
+        statusEl.textContent = `${warning} Click again to play anyway.`;
+        return;
+    }
+    warnedPoint = null;
+
+    try {
+        game.try_play(row, col);
+    } catch (error) {
+        document.getElementById('status').textContent = error.message;
+        return;
+    }
+    renderBoard();
+    updateUI();
+    scheduleComputerMove();
 }
 
 // Test: Guānzǐ (官子) - Endgame test function
@@ -212,8 +568,10 @@ function testGuanzi() {
     console.log('Setting up Guānzǐ (官子) endgame test scenario...');
     console.log('Placing ~100 white stones on left, ~100 black stones on right...');
     
-    // Reset game (starts with Black to play)
-    game.reset();
+    // Reset game on a 19x19 board (starts with Black to play)
+    document.getElementById('size-select').value = '19';
+    document.getElementById('handicap-select').value = '0';
+    newGameFromSettings();
     
     // Generate moves to create endgame scenario
     // Left side (cols 0-9): ~100 white stones
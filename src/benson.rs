@@ -0,0 +1,178 @@
+// Imports: Serialization and game types
+use serde::Serialize;
+
+use crate::{neighbors, Board, GameState, Point, Stone};
+
+// Struct: Chains of one color that are alive even if their owner passes every move (Benson's algorithm),
+// and the vital regions that keep them alive
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct PassAlive {
+    pub chains: Vec<Vec<Point>>,  // Sorted by their first point, each in board order
+    pub regions: Vec<Vec<Point>>, // Sorted by their first point, each in board order
+}
+
+impl GameState {
+    // Pass-Alive: Unconditionally alive chains of player and their vital regions
+    pub fn pass_alive(&self, player: Stone) -> PassAlive {
+        pass_alive(self, player)
+    }
+}
+
+// Pass-Alive: Benson's algorithm on any position
+// Regions are the connected areas not occupied by player; a region is vital to a chain if every empty
+// point in it is a liberty of the chain. Repeatedly drop chains with fewer than two vital regions, then
+// regions that touch a dropped chain, until nothing changes
+pub(crate) fn pass_alive(board: &impl Board, player: Stone) -> PassAlive {
+    if player == Stone::Empty {
+        return PassAlive::default();
+    }
+    let size = board.size();
+    let chains = areas(board, |stone| stone == player);
+    let regions = areas(board, |stone| stone != player);
+    let chain_of = labels(size, &chains);
+
+    // Borders: Chains touching each region, and the chains each region is vital to
+    let mut borders: Vec<Vec<usize>> = Vec::with_capacity(regions.len());
+    let mut vital: Vec<Vec<usize>> = Vec::with_capacity(regions.len());
+    for region in &regions {
+        let mut touching: Vec<usize> = region
+            .iter()
+            .flat_map(|&point| neighbors(size, point))
+            .filter_map(|(row, col)| chain_of[row * size + col])
+            .collect();
+        touching.sort_unstable();
+        touching.dedup();
+        let empty: Vec<Point> =
+            region.iter().copied().filter(|&(row, col)| board.stone(row, col) == Stone::Empty).collect();
+        let vital_to = touching
+            .iter()
+            .copied()
+            .filter(|&chain| {
+                let liberty = |point| neighbors(size, point).any(|(r, c)| chain_of[r * size + c] == Some(chain));
+                empty.iter().all(|&point| liberty(point))
+            })
+            .collect();
+        borders.push(touching);
+        vital.push(vital_to);
+    }
+
+    // Reduce: Drop chains and regions until both sets are stable
+    let mut alive = vec![true; chains.len()];
+    let mut healthy = vec![true; regions.len()];
+    loop {
+        let mut changed = false;
+        for (chain, alive) in alive.iter_mut().enumerate() {
+            let count = (0..regions.len()).filter(|&r| healthy[r] && vital[r].contains(&chain)).count();
+            if *alive && count < 2 {
+                *alive = false;
+                changed = true;
+            }
+        }
+        for (region, healthy) in healthy.iter_mut().enumerate() {
+            if *healthy && borders[region].iter().any(|&chain| !alive[chain]) {
+                *healthy = false;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let regions = regions
+        .into_iter()
+        .enumerate()
+        .filter(|&(r, _)| healthy[r] && vital[r].iter().any(|&chain| alive[chain]))
+        .map(|(_, region)| region)
+        .collect();
+    let chains = chains.into_iter().enumerate().filter(|&(c, _)| alive[c]).map(|(_, chain)| chain).collect();
+    PassAlive { chains, regions }
+}
+
+// Areas: Connected sets of points whose stones satisfy keep, each in board order, sorted by first point
+fn areas(board: &impl Board, keep: impl Fn(Stone) -> bool) -> Vec<Vec<Point>> {
+    let size = board.size();
+    let mut seen = vec![false; size * size];
+    let mut areas = Vec::new();
+    for start in 0..size * size {
+        if seen[start] || !keep(board.stone(start / size, start % size)) {
+            continue;
+        }
+        seen[start] = true;
+        let mut area = Vec::new();
+        let mut stack = vec![(start / size, start % size)];
+        while let Some(point) = stack.pop() {
+            area.push(point);
+            for (row, col) in neighbors(size, point) {
+                if !seen[row * size + col] && keep(board.stone(row, col)) {
+                    seen[row * size + col] = true;
+                    stack.push((row, col));
+                }
+            }
+        }
+        area.sort_unstable();
+        areas.push(area);
+    }
+    areas
+}
+
+// Labels: Index of the area covering each point
+fn labels(size: usize, areas: &[Vec<Point>]) -> Vec<Option<usize>> {
+    let mut labels = vec![None; size * size];
+    for (index, area) in areas.iter().enumerate() {
+        for &(row, col) in area {
+            labels[row * size + col] = Some(index);
+        }
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Position: Black and White stones, with a White wall across row wall
+    fn position(size: usize, wall: usize, black: &[Point], white: &[Point]) -> GameState {
+        let mut stones: Vec<(usize, usize, Stone)> = (0..size).map(|col| (wall, col, Stone::White)).collect();
+        stones.extend(white.iter().map(|&(r, c)| (r, c, Stone::White)));
+        stones.extend(black.iter().map(|&(r, c)| (r, c, Stone::Black)));
+        let mut game = GameState::with_size(size);
+        game.apply_setup(&stones, Stone::Black);
+        game
+    }
+
+    #[test]
+    fn two_eyes_are_pass_alive() {
+        // Eyes: (4, 1) and (4, 3) under a Black wall on row 3
+        let black = [(3, 0), (3, 1), (3, 2), (3, 3), (3, 4), (4, 0), (4, 2), (4, 4)];
+        let alive = position(5, 2, &black, &[]).pass_alive(Stone::Black);
+        let mut chain = black.to_vec();
+        chain.sort_unstable();
+        assert_eq!(alive.chains, vec![chain]);
+        assert_eq!(alive.regions, vec![vec![(4, 1)], vec![(4, 3)]]);
+        assert_eq!(position(5, 2, &black, &[]).pass_alive(Stone::White), PassAlive::default());
+    }
+
+    #[test]
+    fn one_eye_is_not_pass_alive() {
+        // Eye: Straight three along the edge, (4, 1) to (4, 3)
+        let black = [(3, 0), (3, 1), (3, 2), (3, 3), (3, 4), (4, 0), (4, 4)];
+        assert_eq!(position(5, 2, &black, &[]).pass_alive(Stone::Black), PassAlive::default());
+    }
+
+    #[test]
+    fn false_eye_is_not_pass_alive() {
+        // False Eye: (6, 3) joins the two corner chains only through the lone stone at (5, 3), which White's
+        // stones at (5, 2) and (5, 4) cut off; the real eyes are (6, 0) and (6, 6)
+        let black = [(5, 0), (5, 1), (6, 1), (6, 2), (5, 3), (6, 4), (6, 5), (5, 5), (5, 6)];
+        let game = position(7, 4, &black, &[(5, 2), (5, 4)]);
+        assert_eq!(game.pass_alive(Stone::Black), PassAlive::default());
+
+        // Real Eye: With (5, 2) and (5, 4) Black, one chain has three vital regions
+        let mut black = black.to_vec();
+        black.extend([(5, 2), (5, 4)]);
+        let alive = position(7, 4, &black, &[]).pass_alive(Stone::Black);
+        assert_eq!(alive.chains.len(), 1);
+        assert_eq!(alive.regions, vec![vec![(6, 0)], vec![(6, 3)], vec![(6, 6)]]);
+    }
+}
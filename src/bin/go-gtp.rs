@@ -0,0 +1,372 @@
+// GTP: Go Text Protocol v2 engine over stdin/stdout, driving GameState
+// Run with: cargo run --release --bin go-gtp [--level 30k..10k | --playouts N | --time MS] [--seed N]
+use std::io::{self, BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use go_game::{
+    fixed_handicap_points, Difficulty, GamePhase, GameResult, GameState, HandicapPlacement, Mcts, Move, Player,
+    Ruleset, Stone, MAX_BOARD_SIZE, MIN_BOARD_SIZE,
+};
+
+// Constant: Commands answered by this engine (for list_commands / known_command)
+const COMMANDS: &[&str] = &[
+    "protocol_version",
+    "name",
+    "version",
+    "known_command",
+    "list_commands",
+    "quit",
+    "boardsize",
+    "clear_board",
+    "komi",
+    "fixed_handicap",
+    "place_free_handicap",
+    "set_free_handicap",
+    "play",
+    "genmove",
+    "undo",
+    "final_score",
+    "final_status_list",
+    "showboard",
+];
+
+// Constant: GTP column letters ('I' is skipped)
+const COLUMNS: &[u8] = b"ABCDEFGHJKLMNOPQRSTUVWXYZ";
+
+// Constant: Playouts per generated move unless set on the command line
+const DEFAULT_PLAYOUTS: u32 = 1000;
+
+// Constants: Playouts behind the dead-stone estimate, and how surely the opponent must own a stone's point
+const DEAD_STONE_PLAYOUTS: u32 = 400;
+const DEAD_STONE_OWNERSHIP: f64 = 0.2;
+
+// Struct: Engine state - the game plus the bot that generates moves
+struct Engine {
+    game: GameState,
+    bot: Box<dyn Player>,
+}
+
+impl Engine {
+    // Dispatch: Run one command, returning the response text or an error message
+    fn handle(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "protocol_version" => Ok("2".to_string()),
+            "name" => Ok("go_game".to_string()),
+            "version" => Ok(env!("CARGO_PKG_VERSION").to_string()),
+            "known_command" => {
+                let name = args.first().ok_or("syntax error")?;
+                Ok(COMMANDS.contains(name).to_string())
+            }
+            "list_commands" => Ok(COMMANDS.join("\n")),
+            "quit" => Ok(String::new()),
+            "boardsize" => {
+                let size: usize = parse_arg(args, 0)?;
+                if !(MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&size) {
+                    return Err("unacceptable size".to_string());
+                }
+                let komi = self.game.komi();
+                self.game = GameState::with_rules(size, self.game.ruleset());
+                self.game.set_komi(komi);
+                Ok(String::new())
+            }
+            "clear_board" => {
+                // Clear: Empty board even after a handicap game (the controller re-sends handicap)
+                let komi = self.game.komi();
+                self.game.new_handicap(0, HandicapPlacement::Fixed);
+                self.game.set_komi(komi);
+                Ok(String::new())
+            }
+            "komi" => {
+                self.game.set_komi(parse_arg(args, 0)?);
+                Ok(String::new())
+            }
+            "fixed_handicap" | "place_free_handicap" => {
+                let stones: usize = parse_arg(args, 0)?;
+                if !self.stone_vertices().is_empty() {
+                    return Err("board not empty".to_string());
+                }
+                // Free: The engine chooses the standard points, placed as free handicap
+                let size = self.game.size();
+                let points = fixed_handicap_points(size, stones).ok_or("invalid number of stones")?;
+                let placement = match command {
+                    "fixed_handicap" => HandicapPlacement::Fixed,
+                    _ => HandicapPlacement::Free,
+                };
+                let komi = self.game.komi();
+                self.game.new_handicap(stones, placement);
+                self.game.set_komi(komi);
+                if placement == HandicapPlacement::Free {
+                    for &(row, col) in &points {
+                        self.game.place_stone(row, col);
+                    }
+                }
+                let vertices: Vec<String> = points.into_iter().map(|p| format_vertex(Some(p), size)).collect();
+                Ok(vertices.join(" "))
+            }
+            "set_free_handicap" => {
+                if !self.stone_vertices().is_empty() {
+                    return Err("board not empty".to_string());
+                }
+                let size = self.game.size();
+                let points = args
+                    .iter()
+                    .map(|arg| parse_vertex(arg, size)?.ok_or_else(|| "syntax error".to_string()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let komi = self.game.komi();
+                if points.len() < 2 || !self.game.new_handicap(points.len(), HandicapPlacement::Free) {
+                    return Err("bad vertex list".to_string());
+                }
+                self.game.set_komi(komi);
+                for (row, col) in points {
+                    if !self.game.place_stone(row, col) {
+                        self.game.new_handicap(0, HandicapPlacement::Fixed);
+                        self.game.set_komi(komi);
+                        return Err("bad vertex list".to_string());
+                    }
+                }
+                Ok(String::new())
+            }
+            "play" => {
+                let color = parse_color(args.first().ok_or("syntax error")?)?;
+                let point = parse_vertex(args.get(1).ok_or("syntax error")?, self.game.size())?;
+                if self.game.play_as(color, point) {
+                    Ok(String::new())
+                } else {
+                    Err("illegal move".to_string())
+                }
+            }
+            "genmove" => {
+                let color = parse_color(args.first().ok_or("syntax error")?)?;
+                let mut probe = self.game.clone();
+                probe.set_current_player(color);
+                let point = match self.bot.select_move(&probe) {
+                    Move::Play(point) => Some(point),
+                    Move::Pass => None,
+                };
+                if !self.game.play_as(color, point) {
+                    return Err("illegal move".to_string());
+                }
+                Ok(format_vertex(point, self.game.size()))
+            }
+            "undo" => {
+                if self.game.undo() {
+                    Ok(String::new())
+                } else {
+                    Err("cannot undo".to_string())
+                }
+            }
+            "final_score" => {
+                let score = self.settled().score();
+                Ok(GameResult::from_scores(score.black, score.white).to_string())
+            }
+            "final_status_list" => {
+                let status = args.first().ok_or("syntax error")?;
+                // Status: Stones settled dead, stones in seki, and everything else alive
+                let settled = self.settled();
+                let size = settled.size();
+                let dead: Vec<String> =
+                    settled.dead_stones().into_iter().map(|p| format_vertex(Some(p), size)).collect();
+                let seki: Vec<String> = settled
+                    .seki_groups()
+                    .into_iter()
+                    .flatten()
+                    .map(|p| format_vertex(Some(p), size))
+                    .collect();
+                match *status {
+                    "alive" => {
+                        let alive: Vec<String> = self
+                            .stone_vertices()
+                            .into_iter()
+                            .filter(|v| !dead.contains(v) && !seki.contains(v))
+                            .collect();
+                        Ok(alive.join(" "))
+                    }
+                    "dead" => Ok(dead.join(" ")),
+                    "seki" => Ok(seki.join(" ")),
+                    _ => Err("syntax error".to_string()),
+                }
+            }
+            "showboard" => Ok(self.show_board()),
+            _ => Err("unknown command".to_string()),
+        }
+    }
+
+    // Settled: The game as counted at its end - if no stones are marked dead yet, both players pass
+    // (when still playing) and every chain whose points the playout estimate gives to the opponent
+    // is marked dead (seki and pass-alive chains are never marked)
+    fn settled(&self) -> GameState {
+        let mut game = self.game.clone();
+        for _ in 0..3 {
+            if game.phase() != GamePhase::Playing {
+                break;
+            }
+            game.play_as(game.current_player(), None);
+        }
+        if game.phase() != GamePhase::Scoring || !game.dead_stones().is_empty() {
+            return game;
+        }
+        let estimate = game.estimate(DEAD_STONE_PLAYOUTS, 1);
+        let seki: Vec<(usize, usize)> = game.seki_groups().into_iter().flatten().collect();
+        let size = game.size();
+        for (row, col) in (0..size).flat_map(|row| (0..size).map(move |col| (row, col))) {
+            if seki.contains(&(row, col)) {
+                continue;
+            }
+            let lost = match game.get_stone(row, col) {
+                Stone::Black => estimate.owner(row, col) < -DEAD_STONE_OWNERSHIP,
+                Stone::White => estimate.owner(row, col) > DEAD_STONE_OWNERSHIP,
+                Stone::Empty => false,
+            };
+            if lost && !game.is_dead(row, col) {
+                game.toggle_dead_group(row, col);
+            }
+        }
+        game
+    }
+
+    // Vertices: All points holding a stone
+    fn stone_vertices(&self) -> Vec<String> {
+        let size = self.game.size();
+        (0..size)
+            .flat_map(|row| (0..size).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.game.get_stone(row, col) != Stone::Empty)
+            .map(|point| format_vertex(Some(point), size))
+            .collect()
+    }
+
+    // Show Board: ASCII diagram with GTP coordinates
+    fn show_board(&self) -> String {
+        let size = self.game.size();
+        let header: String = COLUMNS[..size].iter().map(|&c| format!(" {}", c as char)).collect();
+        let mut text = format!("\n   {}\n", header);
+        for row in 0..size {
+            let number = size - row;
+            text.push_str(&format!("{:>2} ", number));
+            for col in 0..size {
+                let symbol = match self.game.get_stone(row, col) {
+                    Stone::Black => 'X',
+                    Stone::White => 'O',
+                    Stone::Empty if self.game.is_star_point(row, col) => '+',
+                    Stone::Empty => '.',
+                };
+                text.push_str(&format!(" {}", symbol));
+            }
+            text.push_str(&format!(" {}\n", number));
+        }
+        text.push_str(&format!("   {}\n", header));
+        text.push_str(&format!(
+            "Captured: B {} W {}  Komi {}",
+            self.game.black_captured(),
+            self.game.white_captured(),
+            self.game.komi()
+        ));
+        text
+    }
+}
+
+// Parse: Numeric argument at index
+fn parse_arg<T: std::str::FromStr>(args: &[&str], index: usize) -> Result<T, String> {
+    args.get(index)
+        .and_then(|arg| arg.parse().ok())
+        .ok_or_else(|| "syntax error".to_string())
+}
+
+// Parse: Color argument ("b", "black", "w", "white")
+fn parse_color(arg: &str) -> Result<Stone, String> {
+    match arg.to_ascii_lowercase().as_str() {
+        "b" | "black" => Ok(Stone::Black),
+        "w" | "white" => Ok(Stone::White),
+        _ => Err("syntax error".to_string()),
+    }
+}
+
+// Parse: Vertex such as "D4" or "pass" into (row, col) with row 0 at the top
+fn parse_vertex(arg: &str, size: usize) -> Result<Option<(usize, usize)>, String> {
+    if arg.eq_ignore_ascii_case("pass") {
+        return Ok(None);
+    }
+    let upper = arg.to_ascii_uppercase();
+    let col = upper.bytes().next().and_then(|letter| COLUMNS.iter().position(|&c| c == letter));
+    let number: Option<usize> = upper.get(1..).and_then(|n| n.parse().ok());
+    match (col, number) {
+        (Some(col), Some(number)) if col < size && (1..=size).contains(&number) => {
+            Ok(Some((size - number, col)))
+        }
+        _ => Err("invalid coordinate".to_string()),
+    }
+}
+
+// Format: (row, col) as a GTP vertex, or "pass"
+fn format_vertex(point: Option<(usize, usize)>, size: usize) -> String {
+    match point {
+        Some((row, col)) => format!("{}{}", COLUMNS[col] as char, size - row),
+        None => "pass".to_string(),
+    }
+}
+
+// Options: Bot from the command line (--level 30k..10k, --playouts N, --time MS, --seed N)
+fn bot_from_args() -> Box<dyn Player> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let value = |name: &str| {
+        let index = args.iter().position(|arg| arg == name)?;
+        args.get(index + 1)
+    };
+    let option = |name: &str| value(name).and_then(|value| value.parse::<f64>().ok());
+    let clock_seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(1, |d| d.as_nanos() as u32);
+    let seed = option("--seed").map_or(clock_seed, |seed| seed as u32);
+    if let Some(level) = value("--level").and_then(|name| Difficulty::parse(name)) {
+        return level.player(seed as u64);
+    }
+    match (option("--time"), option("--playouts")) {
+        (Some(time), _) => Box::new(Mcts::with_time_limit(time, seed)),
+        (None, playouts) => Box::new(Mcts::with_playouts(playouts.map_or(DEFAULT_PLAYOUTS, |n| n as u32), seed)),
+    }
+}
+
+// Main: Read commands line by line and write GTP responses
+fn main() {
+    let mut engine = Engine {
+        game: GameState::with_rules(19, Ruleset::Chinese),
+        bot: bot_from_args(),
+    };
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+
+        // Preprocess: Drop comments and control characters, turn tabs into spaces
+        let line: String = line
+            .split('#')
+            .next()
+            .unwrap_or("")
+            .chars()
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        let mut words = line.split_whitespace();
+        let Some(first) = words.next() else { continue };
+
+        // Id: Optional numeric command id echoed in the response
+        let (id, command) = if first.chars().all(|c| c.is_ascii_digit()) {
+            match words.next() {
+                Some(command) => (first, command),
+                None => continue,
+            }
+        } else {
+            ("", first)
+        };
+        let args: Vec<&str> = words.collect();
+
+        let response = match engine.handle(command, &args) {
+            Ok(text) => format!("={} {}", id, text),
+            Err(message) => format!("?{} {}", id, message),
+        };
+        let _ = write!(stdout, "{}\n\n", response.trim_end());
+        let _ = stdout.flush();
+
+        if command == "quit" {
+            break;
+        }
+    }
+}
@@ -0,0 +1,531 @@
+// Imports: Game types, rules and Zobrist keys
+use crate::{
+    zobrist, Board, GameState, IllegalMove, KoRule, Move, MoveOutcome, Point, PositionHistory, Ruleset, Stone,
+    MAX_BOARD_SIZE, MIN_BOARD_SIZE,
+};
+
+// Constant: Words per bit set on the largest board - a padding column after each row stops shifts wrapping between rows
+const WORDS: usize = (MAX_BOARD_SIZE * (MAX_BOARD_SIZE + 1)).div_ceil(64);
+
+// Struct: Fixed-size set of points, bit row * (size + 1) + col, in N words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Bits<const N: usize>([u64; N]);
+
+impl<const N: usize> Bits<N> {
+    const EMPTY: Bits<N> = Bits([0; N]);
+
+    fn single(index: usize) -> Bits<N> {
+        let mut bits = Bits::EMPTY;
+        bits.0[index / 64] |= 1 << (index % 64);
+        bits
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.0[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+
+    fn count(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn and(self, other: Bits<N>) -> Bits<N> {
+        Bits(std::array::from_fn(|i| self.0[i] & other.0[i]))
+    }
+
+    fn or(self, other: Bits<N>) -> Bits<N> {
+        Bits(std::array::from_fn(|i| self.0[i] | other.0[i]))
+    }
+
+    fn and_not(self, other: Bits<N>) -> Bits<N> {
+        Bits(std::array::from_fn(|i| self.0[i] & !other.0[i]))
+    }
+
+    // Shift: Move every bit up by n (0 < n < 64)
+    fn shl(self, n: usize) -> Bits<N> {
+        Bits(std::array::from_fn(|i| {
+            let carry = if i > 0 { self.0[i - 1] >> (64 - n) } else { 0 };
+            (self.0[i] << n) | carry
+        }))
+    }
+
+    // Shift: Move every bit down by n (0 < n < 64)
+    fn shr(self, n: usize) -> Bits<N> {
+        Bits(std::array::from_fn(|i| {
+            let carry = if i + 1 < N { self.0[i + 1] << (64 - n) } else { 0 };
+            (self.0[i] >> n) | carry
+        }))
+    }
+
+    // Row: The len bits from start, as the low bits of a word (len < 64)
+    fn row(&self, start: usize, len: usize) -> u64 {
+        let (word, shift) = (start / 64, start % 64);
+        let mut bits = self.0[word] >> shift;
+        if shift + len > 64 {
+            bits |= self.0[word + 1] << (64 - shift);
+        }
+        bits & ((1 << len) - 1)
+    }
+
+    // Ones: Indexes of set bits, in increasing order
+    fn ones(self) -> Ones<N> {
+        Ones { bits: self.0, word: 0 }
+    }
+}
+
+// Struct: Iterator over the set bits of a Bits, clearing each as it is returned
+struct Ones<const N: usize> {
+    bits: [u64; N],
+    word: usize,
+}
+
+impl<const N: usize> Iterator for Ones<N> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word < N {
+            let bits = &mut self.bits[self.word];
+            if *bits != 0 {
+                let bit = bits.trailing_zeros() as usize;
+                *bits &= *bits - 1;
+                return Some(self.word * 64 + bit);
+            }
+            self.word += 1;
+        }
+        None
+    }
+}
+
+// Struct: Board with one bit set per color, for fast playouts
+// Plays by the same rules as GameState (captures, suicide, ko and superko, passes), without
+// handicap placement, clocks, scoring or a move record
+// Bit sets are sized to the board, so small boards never shift and mask the words of a 25x25 one
+#[derive(Debug, Clone)]
+pub struct Bitboard(Layout);
+
+// Enum: Bitboard for each size class, by words per bit set
+#[derive(Debug, Clone)]
+enum Layout {
+    Small(Grid<2>),  // Up to 10x10
+    Medium(Grid<3>), // Up to 13x13
+    Large(Grid<6>),  // Up to 19x19
+    Huge(Grid<WORDS>),
+}
+
+// Struct: Bitboard state with bit sets of N words
+#[derive(Debug, Clone)]
+struct Grid<const N: usize> {
+    size: usize,
+    width: usize, // Bits per row: size plus the padding column
+    on_board: Bits<N>,
+    black: Bits<N>,
+    white: Bits<N>,
+    to_move: Stone,
+    ruleset: Ruleset,
+    ko_rule: KoRule,
+    hash: u64,
+    previous_hash: Option<u64>,         // Position before the opponent's last move, for ko
+    position_history: PositionHistory, // Zobrist hash + side to move, for superko
+    black_captured: usize,
+    white_captured: usize,
+    consecutive_passes: usize,
+    game_over: bool,
+}
+
+// Struct: A stone's effect on the board, before ko is checked
+struct Resolved<const N: usize> {
+    captured: Bits<N>,
+    suicided: Bits<N>,
+    hash: u64,
+}
+
+impl Bitboard {
+    // Create: Empty NxN board (size clamped to 2..=25), Black to play
+    pub fn new(size: usize, ruleset: Ruleset) -> Bitboard {
+        let size = size.clamp(MIN_BOARD_SIZE, MAX_BOARD_SIZE);
+        let bits = size * (size + 1);
+        Bitboard(match bits {
+            _ if bits <= 2 * 64 => Layout::Small(Grid::new(size, ruleset)),
+            _ if bits <= 3 * 64 => Layout::Medium(Grid::new(size, ruleset)),
+            _ if bits <= 6 * 64 => Layout::Large(Grid::new(size, ruleset)),
+            _ => Layout::Huge(Grid::new(size, ruleset)),
+        })
+    }
+
+    // Create: Copy of a game's current position, player to move, prisoners and ko state
+    pub fn from_game(game: &GameState) -> Bitboard {
+        let mut board = Bitboard::new(game.size, game.ruleset);
+        match &mut board.0 {
+            Layout::Small(grid) => grid.load(game),
+            Layout::Medium(grid) => grid.load(game),
+            Layout::Large(grid) => grid.load(game),
+            Layout::Huge(grid) => grid.load(game),
+        }
+        board
+    }
+
+    pub fn game_over(&self) -> bool {
+        match &self.0 {
+            Layout::Small(grid) => grid.game_over,
+            Layout::Medium(grid) => grid.game_over,
+            Layout::Large(grid) => grid.game_over,
+            Layout::Huge(grid) => grid.game_over,
+        }
+    }
+
+    // Play On: Forget the passes that ended the game, so playouts can run from a finished position
+    pub(crate) fn play_on(&mut self) {
+        match &mut self.0 {
+            Layout::Small(grid) => grid.play_on(),
+            Layout::Medium(grid) => grid.play_on(),
+            Layout::Large(grid) => grid.play_on(),
+            Layout::Huge(grid) => grid.play_on(),
+        }
+    }
+
+    fn grid(&self) -> &dyn Board {
+        match &self.0 {
+            Layout::Small(grid) => grid,
+            Layout::Medium(grid) => grid,
+            Layout::Large(grid) => grid,
+            Layout::Huge(grid) => grid,
+        }
+    }
+
+    fn grid_mut(&mut self) -> &mut dyn Board {
+        match &mut self.0 {
+            Layout::Small(grid) => grid,
+            Layout::Medium(grid) => grid,
+            Layout::Large(grid) => grid,
+            Layout::Huge(grid) => grid,
+        }
+    }
+}
+
+impl<const N: usize> Grid<N> {
+    fn new(size: usize, ruleset: Ruleset) -> Grid<N> {
+        let width = size + 1;
+        let mut on_board = Bits::EMPTY;
+        for row in 0..size {
+            for col in 0..size {
+                on_board = on_board.or(Bits::single(row * width + col));
+            }
+        }
+        Grid {
+            size,
+            width,
+            on_board,
+            black: Bits::EMPTY,
+            white: Bits::EMPTY,
+            to_move: Stone::Black,
+            ruleset,
+            ko_rule: ruleset.ko_rule(),
+            hash: 0,
+            previous_hash: None,
+            position_history: PositionHistory::new(0, Stone::Black),
+            black_captured: 0,
+            white_captured: 0,
+            consecutive_passes: 0,
+            game_over: false,
+        }
+    }
+
+    // Load: Take a game's stones, player to move, prisoners and ko state
+    fn load(&mut self, game: &GameState) {
+        for (row, line) in game.board.iter().enumerate() {
+            for (col, &stone) in line.iter().enumerate() {
+                let bit = Bits::single(row * self.width + col);
+                match stone {
+                    Stone::Black => self.black = self.black.or(bit),
+                    Stone::White => self.white = self.white.or(bit),
+                    Stone::Empty => {}
+                }
+            }
+        }
+        self.to_move = game.current_player;
+        self.ko_rule = game.ko_rule;
+        self.hash = game.chains.hash();
+        self.previous_hash = game.previous_hash;
+        self.position_history = game.position_history.clone();
+        self.black_captured = game.black_captured;
+        self.white_captured = game.white_captured;
+        self.consecutive_passes = game.consecutive_passes;
+        self.game_over = game.game_over;
+    }
+
+    fn play_on(&mut self) {
+        self.consecutive_passes = 0;
+        self.game_over = false;
+    }
+
+    // Neighbors: Points adjacent to any point of the set
+    fn grow(&self, bits: Bits<N>) -> Bits<N> {
+        bits.shl(1).or(bits.shr(1)).or(bits.shl(self.width)).or(bits.shr(self.width)).and(self.on_board)
+    }
+
+    // Flood: The chain of stones in mask connected to seed, or None as soon as it touches one of liberties
+    fn chain_without_liberty(&self, seed: Bits<N>, mask: Bits<N>, liberties: Bits<N>) -> Option<Bits<N>> {
+        let mut chain = seed;
+        loop {
+            let grown = self.grow(chain);
+            if !grown.and(liberties).is_empty() {
+                return None;
+            }
+            let next = chain.or(grown.and(mask));
+            if next == chain {
+                return Some(chain);
+            }
+            chain = next;
+        }
+    }
+
+    // Neighbors: Bits of the points adjacent to one point
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        [index.wrapping_sub(1), index + 1, index.wrapping_sub(self.width), index + self.width]
+            .into_iter()
+            .filter(|&n| n < N * 64 && self.on_board.contains(n))
+    }
+
+    fn empty(&self) -> Bits<N> {
+        self.on_board.and_not(self.black.or(self.white))
+    }
+
+    fn stones(&self, stone: Stone) -> Bits<N> {
+        match stone {
+            Stone::Black => self.black,
+            Stone::White => self.white,
+            Stone::Empty => self.empty(),
+        }
+    }
+
+    // Coordinates: (row, col) of each point of the set, in board order
+    // Rows are counted off as the bits go by, since dividing every index by the width is slow
+    fn coords(&self, bits: Bits<N>) -> impl Iterator<Item = Point> + '_ {
+        let (mut row, mut start) = (0, 0);
+        bits.ones().map(move |index| {
+            while index >= start + self.width {
+                row += 1;
+                start += self.width;
+            }
+            (row, index - start)
+        })
+    }
+
+    // Key: XOR of the Zobrist keys of a set of stones of one color
+    fn keys(&self, bits: Bits<N>, stone: Stone) -> u64 {
+        self.coords(bits).fold(0, |hash, (row, col)| hash ^ zobrist::key(row, col, stone))
+    }
+
+    // Resolve: Captures and suicide for the player to move at (row, col), or why the stone cannot go there
+    fn resolve(&self, row: usize, col: usize) -> Result<Resolved<N>, IllegalMove> {
+        if row >= self.size || col >= self.size {
+            return Err(IllegalMove::OutOfBounds);
+        }
+        let index = row * self.width + col;
+        if !self.empty().contains(index) {
+            return Err(IllegalMove::Occupied);
+        }
+        let (player, opponent) = match self.to_move {
+            Stone::Black => (Stone::Black, Stone::White),
+            Stone::White => (Stone::White, Stone::Black),
+            Stone::Empty => return Err(IllegalMove::GameOver),
+        };
+
+        // Capture: Adjacent opponent chains whose only liberty is this point
+        // A neighbor with another empty point next to it cannot be in atari, so most chains need no flood
+        let point = Bits::single(index);
+        let empty = self.empty().and_not(point);
+        let opponents = self.stones(opponent);
+        let mut captured = Bits::EMPTY;
+        let mut surrounded = true;
+        for neighbor in self.neighbors(index) {
+            if empty.contains(neighbor) {
+                surrounded = false;
+            } else if opponents.contains(neighbor) && !captured.contains(neighbor) {
+                if self.neighbors(neighbor).any(|n| empty.contains(n)) {
+                    continue;
+                }
+                if let Some(chain) = self.chain_without_liberty(Bits::single(neighbor), opponents, empty) {
+                    captured = captured.or(chain);
+                }
+            }
+        }
+
+        // Suicide: The new chain has no liberties, even after captures (never with an empty neighbor)
+        let mut hash = self.hash ^ zobrist::key(row, col, player);
+        let mut suicided = Bits::EMPTY;
+        if !captured.is_empty() {
+            hash ^= self.keys(captured, opponent);
+        } else if surrounded {
+            if let Some(chain) = self.chain_without_liberty(point, self.stones(player), empty) {
+                if !self.ruleset.allows_suicide(chain.count()) {
+                    return Err(IllegalMove::Suicide);
+                }
+                hash ^= self.keys(chain, player);
+                suicided = chain;
+            }
+        }
+        Ok(Resolved { captured, suicided, hash })
+    }
+
+    fn points(&self, bits: Bits<N>) -> Vec<Point> {
+        self.coords(bits).collect()
+    }
+
+    // Play Stone: Place a stone for the player to move, removing captured (or suicided) chains
+    fn play_stone(&mut self, row: usize, col: usize) -> Result<MoveOutcome, IllegalMove> {
+        let resolved = self.resolve(row, col)?;
+        let player = self.to_move;
+        let opponent = self.to_move.opponent();
+        self.ko_rule.check(self.previous_hash, &self.position_history, resolved.hash, opponent)?;
+
+        let point = Bits::single(row * self.width + col);
+        let removed = resolved.captured.or(resolved.suicided);
+        let (own, other) = match player {
+            Stone::Black => (&mut self.black, &mut self.white),
+            _ => (&mut self.white, &mut self.black),
+        };
+        *own = own.or(point).and_not(removed);
+        *other = other.and_not(removed);
+
+        // Prisoners: Captured stones for the player, suicided stones for the opponent
+        let (captured, suicided) = (resolved.captured.count(), resolved.suicided.count());
+        let (own_captured, other_captured) = match player {
+            Stone::Black => (&mut self.black_captured, &mut self.white_captured),
+            _ => (&mut self.white_captured, &mut self.black_captured),
+        };
+        *own_captured += captured;
+        *other_captured += suicided;
+
+        self.previous_hash = Some(self.hash);
+        self.hash = resolved.hash;
+        self.position_history.push(self.hash, opponent);
+        self.consecutive_passes = 0;
+        self.to_move = opponent;
+
+        Ok(MoveOutcome {
+            player,
+            point: Some((row, col)),
+            captured: self.points(resolved.captured),
+            suicided: self.points(resolved.suicided),
+        })
+    }
+
+    // Play Pass: Pass for the player to move (two passes end the game; AGA requires White to pass last)
+    fn play_pass(&mut self) -> MoveOutcome {
+        let player = self.to_move;
+        let next_player = self.to_move.opponent();
+        self.consecutive_passes += 1;
+        self.previous_hash = Some(self.hash);
+        if self.ruleset.pass_stones() {
+            match player {
+                Stone::Black => self.white_captured += 1,
+                _ => self.black_captured += 1,
+            }
+        }
+        self.position_history.push(self.hash, next_player);
+
+        let ends = self.ruleset.ends_on_two_passes() || player == Stone::White;
+        if self.consecutive_passes >= 2 && ends {
+            self.game_over = true;
+        } else {
+            self.to_move = next_player;
+        }
+        MoveOutcome { player, point: None, captured: Vec::new(), suicided: Vec::new() }
+    }
+}
+
+impl<const N: usize> Board for Grid<N> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn stone(&self, row: usize, col: usize) -> Stone {
+        if row >= self.size || col >= self.size {
+            return Stone::Empty;
+        }
+        let index = row * self.width + col;
+        if self.black.contains(index) {
+            Stone::Black
+        } else if self.white.contains(index) {
+            Stone::White
+        } else {
+            Stone::Empty
+        }
+    }
+
+    fn to_move(&self) -> Stone {
+        self.to_move
+    }
+
+    fn prisoners(&self, player: Stone) -> usize {
+        match player {
+            Stone::Black => self.black_captured,
+            Stone::White => self.white_captured,
+            Stone::Empty => 0,
+        }
+    }
+
+    fn fill_empty_points(&self, points: &mut Vec<Point>) {
+        points.clear();
+        let empty = self.empty();
+        for row in 0..self.size {
+            let mut bits = empty.row(row * self.width, self.size);
+            while bits != 0 {
+                points.push((row, bits.trailing_zeros() as usize));
+                bits &= bits - 1;
+            }
+        }
+    }
+
+    fn check_move(&self, row: usize, col: usize) -> Result<(), IllegalMove> {
+        if self.game_over {
+            return Err(IllegalMove::GameOver);
+        }
+        let resolved = self.resolve(row, col)?;
+        self.ko_rule.check(self.previous_hash, &self.position_history, resolved.hash, self.to_move.opponent())
+    }
+
+    fn play(&mut self, mv: Move) -> Result<MoveOutcome, IllegalMove> {
+        if self.game_over {
+            return Err(IllegalMove::GameOver);
+        }
+        match mv {
+            Move::Play((row, col)) => self.play_stone(row, col),
+            Move::Pass => Ok(self.play_pass()),
+        }
+    }
+}
+
+impl Board for Bitboard {
+    fn size(&self) -> usize {
+        self.grid().size()
+    }
+
+    fn stone(&self, row: usize, col: usize) -> Stone {
+        self.grid().stone(row, col)
+    }
+
+    fn to_move(&self) -> Stone {
+        self.grid().to_move()
+    }
+
+    fn prisoners(&self, player: Stone) -> usize {
+        self.grid().prisoners(player)
+    }
+
+    fn fill_empty_points(&self, points: &mut Vec<Point>) {
+        self.grid().fill_empty_points(points)
+    }
+
+    fn check_move(&self, row: usize, col: usize) -> Result<(), IllegalMove> {
+        self.grid().check_move(row, col)
+    }
+
+    fn play(&mut self, mv: Move) -> Result<MoveOutcome, IllegalMove> {
+        self.grid_mut().play(mv)
+    }
+}
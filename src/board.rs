@@ -0,0 +1,119 @@
+// Imports: Game types
+use crate::{neighbors, GameState, IllegalMove, Move, MoveOutcome, Point, Rng, Stone};
+
+// Trait: A Go position that can be played on - implemented by GameState and Bitboard,
+// so the two representations can be driven by the same code and cross-checked
+pub trait Board {
+    // Size: Board is size x size
+    fn size(&self) -> usize;
+
+    // Stone: Stone at (row, col) (Empty off the board)
+    fn stone(&self, row: usize, col: usize) -> Stone;
+
+    // To Move: Player to move
+    fn to_move(&self) -> Stone;
+
+    // Prisoners: Stones captured by the player (plus pass stones, per ruleset)
+    fn prisoners(&self, player: Stone) -> usize;
+
+    // Check: Why a stone at (row, col) would be illegal for the player to move (Ok if legal)
+    fn check_move(&self, row: usize, col: usize) -> Result<(), IllegalMove>;
+
+    // Play: Play a stone or pass for the player to move
+    fn play(&mut self, mv: Move) -> Result<MoveOutcome, IllegalMove>;
+
+    // Empty Points: Every empty point, in board order
+    fn empty_points(&self) -> Vec<Point> {
+        let mut points = Vec::new();
+        self.fill_empty_points(&mut points);
+        points
+    }
+
+    // Empty Points: Replace the contents of points with every empty point, in board order
+    // Reusing one buffer keeps a playout from allocating on every move
+    fn fill_empty_points(&self, points: &mut Vec<Point>) {
+        let size = self.size();
+        points.clear();
+        points.extend(
+            (0..size)
+                .flat_map(|row| (0..size).map(move |col| (row, col)))
+                .filter(|&(row, col)| self.stone(row, col) == Stone::Empty),
+        );
+    }
+
+    // Legal Moves: Every point the player to move may play on
+    fn legal_moves(&self) -> Vec<Point> {
+        let size = self.size();
+        (0..size)
+            .flat_map(|row| (0..size).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.check_move(row, col).is_ok())
+            .collect()
+    }
+
+    // Eye: Empty point whose neighbors are all the player's stones
+    fn is_own_eye(&self, row: usize, col: usize, player: Stone) -> bool {
+        neighbors(self.size(), (row, col)).all(|(r, c)| self.stone(r, c) == player)
+    }
+
+    // Random Move: Random legal move that does not fill one of the player's own eyes, else pass (the playout
+    // policy); tries empty points in random order until one is legal
+    fn random_move(&self, rng: &mut Rng) -> Move {
+        let player = self.to_move();
+        let mut empty = self.empty_points();
+        while !empty.is_empty() {
+            let (row, col) = empty.swap_remove(rng.below(empty.len()));
+            if !self.is_own_eye(row, col, player) && self.check_move(row, col).is_ok() {
+                return Move::Play((row, col));
+            }
+        }
+        Move::Pass
+    }
+
+    // Play Random Move: Play what random_move would choose, trying each point by playing it so the move kept is
+    // not worked out twice; empty is reused between calls. False once the game is over
+    fn play_random_move(&mut self, rng: &mut Rng, empty: &mut Vec<Point>) -> bool {
+        let player = self.to_move();
+        self.fill_empty_points(empty);
+        while !empty.is_empty() {
+            let (row, col) = empty.swap_remove(rng.below(empty.len()));
+            if !self.is_own_eye(row, col, player) && self.play(Move::Play((row, col))).is_ok() {
+                return true;
+            }
+        }
+        self.play(Move::Pass).is_ok()
+    }
+}
+
+impl Board for GameState {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn stone(&self, row: usize, col: usize) -> Stone {
+        self.get_stone(row, col)
+    }
+
+    fn to_move(&self) -> Stone {
+        self.current_player
+    }
+
+    fn prisoners(&self, player: Stone) -> usize {
+        match player {
+            Stone::Black => self.black_captured,
+            Stone::White => self.white_captured,
+            Stone::Empty => 0,
+        }
+    }
+
+    fn check_move(&self, row: usize, col: usize) -> Result<(), IllegalMove> {
+        GameState::check_move(self, row, col)
+    }
+
+    fn play(&mut self, mv: Move) -> Result<MoveOutcome, IllegalMove> {
+        self.try_play(mv)
+    }
+
+    fn legal_moves(&self) -> Vec<Point> {
+        self.valid_moves()
+    }
+}
@@ -0,0 +1,252 @@
+// Imports: WebAssembly bindings, time sources and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use std::sync::Arc;
+
+use crate::benson::pass_alive;
+use crate::{
+    fixed_handicap_points, territory_map, Bitboard, Board, GameState, Move, Point, Score, Stone, SystemTime,
+    TimeSource,
+};
+
+// Constant: UCT exploration constant (higher tries more moves, lower digs deeper into the best ones)
+const EXPLORATION: f64 = 1.0;
+
+// Struct: Seedable pseudo-random number generator (xorshift64*), the same sequence for the same seed
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Seed: Spread the seed with SplitMix64 so small seeds differ quickly (state must not be zero)
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Rng((z ^ (z >> 31)) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Below: Uniform number in 0..n (n > 0)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+// Struct: Monte Carlo Tree Search (UCT) player with light random playouts
+// Searches for a number of playouts or a time budget per move, whichever is set
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Mcts {
+    playouts: u32,   // Playouts per move (0 = no limit)
+    time_limit: f64, // Milliseconds per move (0 = no limit)
+    rng: Rng,
+    source: Arc<dyn TimeSource>,
+}
+
+// Struct: Search tree node - the move leading to it, its statistics and the moves not yet expanded
+struct Node {
+    mv: Move,
+    player: Stone, // Player who made mv
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried: Vec<Move>,
+    visits: f64,
+    wins: f64, // Playouts won by player (draws count half)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Mcts {
+    // Constructors: Fixed number of playouts per move, or a time budget per move in milliseconds
+    pub fn with_playouts(playouts: u32, seed: u32) -> Mcts {
+        Mcts { playouts: playouts.max(1), time_limit: 0.0, rng: Rng::new(seed as u64), source: Arc::new(SystemTime) }
+    }
+
+    pub fn with_time_limit(time_limit: f64, seed: u32) -> Mcts {
+        let time_limit = time_limit.max(1.0);
+        Mcts { playouts: 0, time_limit, rng: Rng::new(seed as u64), source: Arc::new(SystemTime) }
+    }
+}
+
+impl Mcts {
+    // Time Source: Clock the time budget is measured on
+    pub fn set_time_source(&mut self, source: Arc<dyn TimeSource>) {
+        self.source = source;
+    }
+
+    // Search: Best move for the player to move (the most visited reply at the root)
+    pub fn search(&mut self, game: &GameState) -> Move {
+        if game.game_over() {
+            return Move::Pass;
+        }
+        if game.handicap_to_place() > 0 {
+            return handicap_move(game);
+        }
+
+        let root = Bitboard::from_game(game);
+        let komi = game.komi();
+        let opponent = root.to_move().opponent();
+        let mut moves = candidates(&root);
+        let settled = settled_points(&root);
+        moves.retain(|mv| !matches!(mv, Move::Play(point) if settled.contains(point)));
+        let mut nodes = vec![Node::new(Move::Pass, opponent, None, moves)];
+        let start = self.source.now();
+        let mut playouts = 0;
+
+        while !self.finished(playouts, start) {
+            let mut board = root.clone();
+
+            // Select: Follow the best UCT child while every move at the node has been tried
+            let mut node = 0;
+            while nodes[node].untried.is_empty() && !nodes[node].children.is_empty() {
+                node = select(&nodes, node);
+                let _ = board.play(nodes[node].mv);
+            }
+
+            // Expand: Add one untried move, chosen at random
+            if !nodes[node].untried.is_empty() {
+                let index = self.rng.below(nodes[node].untried.len());
+                let mv = nodes[node].untried.swap_remove(index);
+                let player = board.to_move();
+                let _ = board.play(mv);
+                let child = nodes.len();
+                nodes.push(Node::new(mv, player, Some(node), candidates(&board)));
+                nodes[node].children.push(child);
+                node = child;
+            }
+
+            // Playout: Random game to the end, then credit every node on the path
+            let winner = playout(&mut board, komi, &mut self.rng);
+            let mut current = Some(node);
+            while let Some(id) = current {
+                let node = &mut nodes[id];
+                node.visits += 1.0;
+                node.wins += match winner {
+                    Stone::Empty => 0.5,
+                    w if w == node.player => 1.0,
+                    _ => 0.0,
+                };
+                current = node.parent;
+            }
+            playouts += 1;
+        }
+
+        nodes[0]
+            .children
+            .iter()
+            .max_by(|&&a, &&b| nodes[a].visits.total_cmp(&nodes[b].visits))
+            .map_or(Move::Pass, |&child| nodes[child].mv)
+    }
+
+    // Budget: Whether the playout count or time limit has been reached (always at least one playout)
+    fn finished(&self, playouts: u32, start: f64) -> bool {
+        if playouts == 0 {
+            return false;
+        }
+        (self.playouts > 0 && playouts >= self.playouts)
+            || (self.time_limit > 0.0 && self.source.now() - start >= self.time_limit)
+    }
+}
+
+impl Node {
+    fn new(mv: Move, player: Stone, parent: Option<usize>, untried: Vec<Move>) -> Node {
+        Node { mv, player, parent, children: Vec::new(), untried, visits: 0.0, wins: 0.0 }
+    }
+}
+
+// Select: Child with the highest UCB1 value (win rate plus exploration bonus)
+fn select(nodes: &[Node], node: usize) -> usize {
+    let log_visits = nodes[node].visits.ln();
+    let uct = |child: usize| {
+        let child = &nodes[child];
+        child.wins / child.visits + EXPLORATION * (log_visits / child.visits).sqrt()
+    };
+    let children = &nodes[node].children;
+    children.iter().copied().max_by(|&a, &b| uct(a).total_cmp(&uct(b))).unwrap_or(node)
+}
+
+// Candidates: Legal moves that do not fill one of the player's own eyes, plus a pass (none once the game is over)
+fn candidates(board: &Bitboard) -> Vec<Move> {
+    if board.game_over() {
+        return Vec::new();
+    }
+    let player = board.to_move();
+    let mut moves: Vec<Move> = board
+        .legal_moves()
+        .into_iter()
+        .filter(|&(row, col)| !board.is_own_eye(row, col, player))
+        .map(Move::Play)
+        .collect();
+    moves.push(Move::Pass);
+    moves
+}
+
+// Settled: Vital regions of either color's pass-alive chains, where a move changes nothing
+fn settled_points(board: &Bitboard) -> Vec<Point> {
+    [Stone::Black, Stone::White]
+        .into_iter()
+        .flat_map(|player| pass_alive(board, player).regions)
+        .flatten()
+        .collect()
+}
+
+// Playout: Play random moves until the game ends (or a move limit), returning the winner (Empty on a draw)
+pub(crate) fn playout(board: &mut Bitboard, komi: f64, rng: &mut Rng) -> Stone {
+    let limit = board.size() * board.size() * 2;
+    let mut empty = Vec::with_capacity(board.size() * board.size());
+    for _ in 0..limit {
+        if board.game_over() {
+            break;
+        }
+        board.play_random_move(rng, &mut empty);
+    }
+    let score = area_score(board, komi);
+    match score.black - score.white {
+        margin if margin > 0.0 => Stone::Black,
+        margin if margin < 0.0 => Stone::White,
+        _ => Stone::Empty,
+    }
+}
+
+// Area Score: Stones plus surrounded empty points for each color, komi for White (all stones alive)
+pub(crate) fn area_score(board: &impl Board, komi: f64) -> Score {
+    let size = board.size();
+    let grid: Vec<Vec<Stone>> = (0..size).map(|row| (0..size).map(|col| board.stone(row, col)).collect()).collect();
+    let territory = territory_map(&grid, size);
+    let count = |player: Stone| {
+        let stones = grid.iter().flatten().filter(|&&s| s == player).count();
+        let area = territory.iter().flatten().filter(|&&s| s == player).count();
+        (stones + area) as f64
+    };
+    Score { black: count(Stone::Black), white: count(Stone::White) + komi }
+}
+
+// Handicap: Next standard handicap point still empty (free placement), else the first empty point
+pub(crate) fn handicap_move(game: &GameState) -> Move {
+    let size = game.size();
+    let points = fixed_handicap_points(size, game.handicap()).unwrap_or_default();
+    let all = (0..size).flat_map(|row| (0..size).map(move |col| (row, col)));
+    points
+        .into_iter()
+        .chain(all)
+        .find(|&(row, col)| game.get_stone(row, col) == Stone::Empty)
+        .map_or(Move::Pass, Move::Play)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mcts_runs_on_a_worker_thread() {
+        let game = GameState::with_size(9);
+        let mut bot = Mcts::with_playouts(50, 7);
+        let mv = std::thread::spawn(move || bot.search(&game)).join().unwrap();
+        assert!(matches!(mv, Move::Play(_)));
+    }
+}
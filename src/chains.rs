@@ -0,0 +1,315 @@
+// Imports: Game types and Zobrist keys
+use crate::{zobrist, Point, Stone, MAX_BOARD_SIZE};
+
+// Struct: Board kept as chains - each stone links to the next stone of its chain (circular list),
+// and each chain's head holds its size, Zobrist keys and liberty set, updated as stones are played
+#[derive(Debug, Clone)]
+pub(crate) struct Chains {
+    size: usize,
+    words: usize,        // u64 words per liberty bitset
+    stones: Vec<Stone>,
+    head: Vec<usize>,    // Chain head of each stone
+    next: Vec<usize>,    // Next stone in the same chain
+    count: Vec<usize>,   // Stones in the chain (at the head)
+    keys: Vec<u64>,      // XOR of the chain's Zobrist keys (at the head)
+    liberties: Vec<u64>, // Liberty bitset of the chain (at the head)
+    hash: u64,           // Zobrist hash of the whole board
+}
+
+// Struct: What playing a stone would do, worked out without changing the board
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Probe {
+    pub suicide: bool, // Captures nothing and leaves the new chain without liberties
+    pub group: usize,  // Size of the chain the new stone joins
+    pub hash: u64,     // Zobrist hash after the move (own chain removed on suicide)
+}
+
+impl Chains {
+    // Create: Empty board
+    pub(crate) fn new(size: usize) -> Chains {
+        let points = size * size;
+        let words = points.div_ceil(64);
+        Chains {
+            size,
+            words,
+            stones: vec![Stone::Empty; points],
+            head: (0..points).collect(),
+            next: (0..points).collect(),
+            count: vec![0; points],
+            keys: vec![0; points],
+            liberties: vec![0; points * words],
+            hash: 0,
+        }
+    }
+
+    // Create: Chains for an arbitrary position (no captures are made)
+    pub(crate) fn from_board(board: &[Vec<Stone>]) -> Chains {
+        let mut chains = Chains::new(board.len());
+        for (row, line) in board.iter().enumerate() {
+            for (col, &stone) in line.iter().enumerate() {
+                if stone != Stone::Empty {
+                    chains.add_stone(row * chains.size + col, stone);
+                }
+            }
+        }
+        chains
+    }
+
+    // Hash: Zobrist hash of the board
+    pub(crate) fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    // Stone: Stone at (row, col)
+    pub(crate) fn stone(&self, row: usize, col: usize) -> Stone {
+        self.stones[row * self.size + col]
+    }
+
+    // Liberties: Liberties of the chain at (row, col) (0 for an empty point)
+    pub(crate) fn liberties(&self, row: usize, col: usize) -> usize {
+        let point = row * self.size + col;
+        match self.stones[point] {
+            Stone::Empty => 0,
+            _ => self.liberty_count(self.head[point]),
+        }
+    }
+
+    // Liberties After: Liberties of the chain stone would form at the empty point (row, col), ignoring captures
+    pub(crate) fn liberties_after(&self, row: usize, col: usize, stone: Stone) -> usize {
+        let point = row * self.size + col;
+        let mut liberties = [0u64; (MAX_BOARD_SIZE * MAX_BOARD_SIZE).div_ceil(64)];
+        for neighbor in neighbors(self.size, point) {
+            if self.stones[neighbor] == Stone::Empty {
+                liberties[neighbor / 64] |= 1 << (neighbor % 64);
+            } else if self.stones[neighbor] == stone {
+                let start = self.head[neighbor] * self.words;
+                for (word, chain) in liberties.iter_mut().zip(&self.liberties[start..start + self.words]) {
+                    *word |= chain;
+                }
+            }
+        }
+        liberties[point / 64] &= !(1 << (point % 64));
+        liberties.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // Liberty Points: Liberties of the chain at (row, col), in board order (empty for an empty point)
+    pub(crate) fn liberty_points(&self, row: usize, col: usize) -> Vec<Point> {
+        let point = row * self.size + col;
+        if self.stones[point] == Stone::Empty {
+            return Vec::new();
+        }
+        let start = self.head[point] * self.words;
+        let mut points = Vec::new();
+        for (word, &bits) in self.liberties[start..start + self.words].iter().enumerate() {
+            let mut bits = bits;
+            while bits != 0 {
+                let liberty = word * 64 + bits.trailing_zeros() as usize;
+                points.push((liberty / self.size, liberty % self.size));
+                bits &= bits - 1;
+            }
+        }
+        points
+    }
+
+    // Capturing Points: Last liberties of opponent chains in atari touching the chain at (row, col), in board order
+    pub(crate) fn capturing_points(&self, row: usize, col: usize) -> Vec<Point> {
+        let point = row * self.size + col;
+        let stone = self.stones[point];
+        let mut points = Vec::new();
+        if stone == Stone::Empty {
+            return points;
+        }
+        for member in self.members(point) {
+            for neighbor in neighbors(self.size, member) {
+                let other = self.stones[neighbor];
+                if other != Stone::Empty && other != stone && self.liberty_count(self.head[neighbor]) == 1 {
+                    points.extend(self.liberty_points(neighbor / self.size, neighbor % self.size));
+                }
+            }
+        }
+        points.sort_unstable();
+        points.dedup();
+        points
+    }
+
+    // Chain: Stones of the chain at (row, col), in board order (empty for an empty point)
+    pub(crate) fn chain(&self, row: usize, col: usize) -> Vec<Point> {
+        let point = row * self.size + col;
+        if self.stones[point] == Stone::Empty {
+            return Vec::new();
+        }
+        let mut points: Vec<Point> = self.members(point).map(|p| (p / self.size, p % self.size)).collect();
+        points.sort_unstable();
+        points
+    }
+
+    // Probe: Outcome of stone playing at the empty point (row, col)
+    pub(crate) fn probe(&self, row: usize, col: usize, stone: Stone) -> Probe {
+        let point = row * self.size + col;
+        let key = zobrist::key(row, col, stone);
+        let mut hash = self.hash ^ key;
+        let mut own_keys = key;
+        let mut captured = 0;
+        let mut group = 1;
+        let mut liberty = false;
+
+        // Neighbors: Each adjacent chain is counted once
+        let mut seen = [usize::MAX; 4];
+        for (i, neighbor) in neighbors(self.size, point).enumerate() {
+            if self.stones[neighbor] == Stone::Empty {
+                liberty = true;
+                continue;
+            }
+            let head = self.head[neighbor];
+            if seen.contains(&head) {
+                continue;
+            }
+            seen[i] = head;
+            let liberties = self.liberty_count(head);
+            if self.stones[neighbor] == stone {
+                // Friend: Joins the new chain, and keeps a liberty if it has one besides this point
+                group += self.count[head];
+                own_keys ^= self.keys[head];
+                liberty |= liberties > 1;
+            } else if liberties == 1 {
+                // Opponent: In atari on this point, so captured
+                captured += self.count[head];
+                hash ^= self.keys[head];
+            }
+        }
+
+        let suicide = captured == 0 && !liberty;
+        if suicide {
+            hash ^= own_keys;
+        }
+        Probe { suicide, group, hash }
+    }
+
+    // Play: Put stone on the empty point (row, col), then remove captured chains and, on suicide, its own chain
+    // Returns (captured, suicided) points in board order
+    pub(crate) fn play(&mut self, row: usize, col: usize, stone: Stone) -> (Vec<Point>, Vec<Point>) {
+        let point = row * self.size + col;
+        self.add_stone(point, stone);
+
+        let mut captured = Vec::new();
+        for neighbor in neighbors(self.size, point) {
+            let other = self.stones[neighbor];
+            if other != Stone::Empty && other != stone && self.liberty_count(self.head[neighbor]) == 0 {
+                captured.extend(self.remove_chain(self.head[neighbor]));
+            }
+        }
+
+        let mut suicided = Vec::new();
+        if self.liberty_count(self.head[point]) == 0 {
+            suicided = self.remove_chain(self.head[point]);
+        }
+        captured.sort_unstable();
+        suicided.sort_unstable();
+        (captured, suicided)
+    }
+
+    // Add: Place a stone as a one-stone chain, take its point from adjacent chains' liberties, and merge friends
+    fn add_stone(&mut self, point: usize, stone: Stone) {
+        let key = zobrist::key(point / self.size, point % self.size, stone);
+        self.stones[point] = stone;
+        self.hash ^= key;
+        self.head[point] = point;
+        self.next[point] = point;
+        self.count[point] = 1;
+        self.keys[point] = key;
+        self.set_liberties(point).fill(0);
+
+        for neighbor in neighbors(self.size, point) {
+            match self.stones[neighbor] {
+                Stone::Empty => self.set_liberty(point, neighbor, true),
+                _ => self.set_liberty(self.head[neighbor], point, false),
+            }
+        }
+        for neighbor in neighbors(self.size, point) {
+            if self.stones[neighbor] == stone && self.head[neighbor] != self.head[point] {
+                self.merge(self.head[point], self.head[neighbor]);
+            }
+        }
+    }
+
+    // Merge: Join two chains, relabelling the smaller one
+    fn merge(&mut self, a: usize, b: usize) {
+        let (keep, absorb) = if self.count[a] >= self.count[b] { (a, b) } else { (b, a) };
+        let mut stone = absorb;
+        loop {
+            self.head[stone] = keep;
+            stone = self.next[stone];
+            if stone == absorb {
+                break;
+            }
+        }
+        self.next.swap(keep, absorb);
+        self.count[keep] += self.count[absorb];
+        self.keys[keep] ^= self.keys[absorb];
+        for word in 0..self.words {
+            self.liberties[keep * self.words + word] |= self.liberties[absorb * self.words + word];
+        }
+    }
+
+    // Remove: Take a chain off the board, giving its points back as liberties to adjacent chains
+    fn remove_chain(&mut self, head: usize) -> Vec<Point> {
+        let removed: Vec<usize> = self.members(head).collect();
+        for &point in &removed {
+            self.stones[point] = Stone::Empty;
+            self.count[point] = 0;
+        }
+        self.hash ^= self.keys[head];
+        for &point in &removed {
+            for neighbor in neighbors(self.size, point) {
+                if self.stones[neighbor] != Stone::Empty {
+                    self.set_liberty(self.head[neighbor], point, true);
+                }
+            }
+        }
+        removed.into_iter().map(|p| (p / self.size, p % self.size)).collect()
+    }
+
+    // Members: Stones of the chain containing point
+    fn members(&self, point: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut current = Some(point);
+        std::iter::from_fn(move || {
+            let stone = current?;
+            let next = self.next[stone];
+            current = Some(next).filter(|&n| n != point);
+            Some(stone)
+        })
+    }
+
+    // Liberties: Number of points in a chain's liberty set
+    fn liberty_count(&self, head: usize) -> usize {
+        let start = head * self.words;
+        self.liberties[start..start + self.words].iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn set_liberties(&mut self, head: usize) -> &mut [u64] {
+        let start = head * self.words;
+        &mut self.liberties[start..start + self.words]
+    }
+
+    fn set_liberty(&mut self, head: usize, point: usize, liberty: bool) {
+        let word = &mut self.set_liberties(head)[point / 64];
+        if liberty {
+            *word |= 1 << (point % 64);
+        } else {
+            *word &= !(1 << (point % 64));
+        }
+    }
+}
+
+// Neighbors: Adjacent point indexes (up, down, left, right) on a board of given size
+pub(crate) fn neighbors(size: usize, point: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (point / size, point % size);
+    [
+        (row > 0).then(|| point - size),
+        (row + 1 < size).then(|| point + size),
+        (col > 0).then(|| point - 1),
+        (col + 1 < size).then(|| point + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
@@ -0,0 +1,507 @@
+// Imports: WebAssembly bindings, serialization, and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::{GamePhase, GameResult, GameState, ResultReason, Stone};
+
+// Enum: Time system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum TimeSystem {
+    Absolute, // Main time only
+    Fischer,  // Main time, plus an increment after every move
+    ByoYomi,  // Main time, then a number of periods; a period is used up only if a move overruns it
+    Canadian, // Main time, then a block of time for a number of stones
+    Simple,   // Fixed time for every move
+}
+
+// Struct: Time control settings (all times in milliseconds)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct TimeControl {
+    pub system: TimeSystem,
+    pub main_time: f64,
+    pub period_time: f64, // Fischer increment, byo-yomi period, Canadian block, or time per move
+    pub periods: u32,     // Byo-yomi periods
+    pub stones: u32,      // Stones to play per Canadian block
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TimeControl {
+    // Constructors: One per time system
+    pub fn absolute(main_time: f64) -> TimeControl {
+        TimeControl { system: TimeSystem::Absolute, main_time, period_time: 0.0, periods: 0, stones: 0 }
+    }
+
+    pub fn fischer(main_time: f64, increment: f64) -> TimeControl {
+        TimeControl { system: TimeSystem::Fischer, main_time, period_time: increment, periods: 0, stones: 0 }
+    }
+
+    pub fn byo_yomi(main_time: f64, period_time: f64, periods: u32) -> TimeControl {
+        TimeControl { system: TimeSystem::ByoYomi, main_time, period_time, periods, stones: 0 }
+    }
+
+    pub fn canadian(main_time: f64, period_time: f64, stones: u32) -> TimeControl {
+        TimeControl { system: TimeSystem::Canadian, main_time, period_time, periods: 0, stones }
+    }
+
+    pub fn simple(time_per_move: f64) -> TimeControl {
+        let period_time = time_per_move;
+        TimeControl { system: TimeSystem::Simple, main_time: 0.0, period_time, periods: 0, stones: 0 }
+    }
+}
+
+// Trait: Source of the current time in milliseconds (swap in ManualTime for deterministic clocks)
+// Sources are shared between threads, so games and bots can run on worker threads
+pub trait TimeSource: fmt::Debug + Send + Sync {
+    fn now(&self) -> f64;
+}
+
+// Time Source: Wall clock (Date.now() in the browser, system time natively)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemTime;
+
+impl TimeSource for SystemTime {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    fn now(&self) -> f64 {
+        js_sys::Date::now()
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    fn now(&self) -> f64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |d| d.as_secs_f64() * 1000.0)
+    }
+}
+
+// Time Source: Time that only moves when told to; clones share the same time
+#[derive(Debug, Clone, Default)]
+pub struct ManualTime(Arc<Mutex<f64>>);
+
+impl ManualTime {
+    pub fn new() -> ManualTime {
+        ManualTime::default()
+    }
+
+    // Advance: Move time forward by ms milliseconds
+    pub fn advance(&self, ms: f64) {
+        *self.0.lock().unwrap() += ms;
+    }
+}
+
+impl TimeSource for ManualTime {
+    fn now(&self) -> f64 {
+        *self.0.lock().unwrap()
+    }
+}
+
+// Struct: One player's remaining time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PlayerTime {
+    pub main_time: f64,
+    pub period_time: f64, // Time left in the current byo-yomi period / Canadian block
+    pub periods: u32,     // Byo-yomi periods left
+    pub stones: u32,      // Stones left to play in the current Canadian block
+    pub flagged: bool,
+}
+
+// Struct: Clock for both players - one side runs at a time, switched by each move
+#[derive(Debug, Clone)]
+pub struct GameClock {
+    control: TimeControl,
+    black: PlayerTime,
+    white: PlayerTime,
+    running: Option<Stone>,
+    started_at: f64, // Time source reading when the running side's clock was started
+    source: Arc<dyn TimeSource>,
+}
+
+impl GameClock {
+    // Create: Stopped clock reading the wall clock
+    pub fn new(control: TimeControl) -> GameClock {
+        GameClock::with_source(control, Arc::new(SystemTime))
+    }
+
+    // Create: Stopped clock reading the given time source
+    pub fn with_source(control: TimeControl, source: Arc<dyn TimeSource>) -> GameClock {
+        let time = PlayerTime {
+            main_time: control.main_time,
+            period_time: control.period_time,
+            periods: control.periods,
+            stones: control.stones,
+            flagged: false,
+        };
+        GameClock { control, black: time, white: time, running: None, started_at: 0.0, source }
+    }
+
+    // Reset: Full time for both players again, stopped
+    pub fn reset(&mut self) {
+        *self = GameClock::with_source(self.control, self.source.clone());
+    }
+
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    // Running: Side whose clock is running (None when stopped)
+    pub fn running(&self) -> Option<Stone> {
+        self.running
+    }
+
+    // Start: Run the player's clock from now (stops the other side without charging it)
+    pub fn start(&mut self, player: Stone) {
+        if player != Stone::Empty {
+            self.running = Some(player);
+            self.started_at = self.source.now();
+        }
+    }
+
+    // Stop: Charge the running side for its move so far and stop the clock
+    // Returns false if that side ran out of time
+    pub fn stop(&mut self) -> bool {
+        let in_time = match self.running {
+            Some(player) => {
+                let elapsed = self.source.now() - self.started_at;
+                let control = self.control;
+                let time = self.player_mut(player);
+                let in_time = spend(&control, time, elapsed);
+                if in_time {
+                    complete_move(&control, time);
+                }
+                in_time
+            }
+            None => true,
+        };
+        self.running = None;
+        in_time
+    }
+
+    // Press: The player finished a move - charge it, apply increments, and start the opponent
+    // Returns false if the player ran out of time (the clock is then stopped)
+    pub fn press(&mut self, player: Stone) -> bool {
+        if player == Stone::Empty {
+            return true;
+        }
+        let opponent = player.opponent();
+        if self.running == Some(player) && !self.stop() {
+            return false;
+        }
+        self.start(opponent);
+        true
+    }
+
+    // Time: A player's remaining time, counting the running side's time so far
+    pub fn time(&self, player: Stone) -> PlayerTime {
+        let mut time = match player {
+            Stone::White => self.white,
+            _ => self.black,
+        };
+        if self.running == Some(player) {
+            let elapsed = self.source.now() - self.started_at;
+            spend(&self.control, &mut time, elapsed);
+        }
+        time
+    }
+
+    // Flag: The running side if its time has run out
+    pub fn flagged(&self) -> Option<Stone> {
+        self.running.filter(|&player| self.time(player).flagged)
+    }
+
+    fn player_mut(&mut self, player: Stone) -> &mut PlayerTime {
+        match player {
+            Stone::White => &mut self.white,
+            _ => &mut self.black,
+        }
+    }
+}
+
+// Spend: Take elapsed milliseconds of thinking time on the current move off a player's time
+// Returns false (and marks the player flagged) if that overruns the time available
+fn spend(control: &TimeControl, time: &mut PlayerTime, elapsed: f64) -> bool {
+    let budget = match control.system {
+        TimeSystem::Absolute | TimeSystem::Fischer => time.main_time,
+        TimeSystem::ByoYomi => time.main_time + time.periods as f64 * control.period_time,
+        TimeSystem::Canadian => time.main_time + time.period_time,
+        TimeSystem::Simple => time.period_time,
+    };
+    if elapsed > budget {
+        time.flagged = true;
+        time.main_time = 0.0;
+        time.period_time = 0.0;
+        time.periods = 0;
+        return false;
+    }
+
+    // Overtime: Whatever main time cannot cover
+    let overtime = (elapsed - time.main_time).max(0.0);
+    time.main_time = (time.main_time - elapsed).max(0.0);
+    match control.system {
+        TimeSystem::Absolute | TimeSystem::Fischer => {}
+        TimeSystem::Simple => time.period_time -= elapsed,
+        TimeSystem::ByoYomi if overtime > 0.0 => {
+            // Periods: Every period used up is lost, leaving the rest of the current one
+            let started = (overtime / control.period_time).ceil() as u32;
+            time.periods -= started - 1;
+            time.period_time = started as f64 * control.period_time - overtime;
+        }
+        TimeSystem::ByoYomi => {}
+        TimeSystem::Canadian => time.period_time -= overtime,
+    }
+    true
+}
+
+// Complete: A move was made in time - add the Fischer increment, reset per-move periods, count Canadian stones
+fn complete_move(control: &TimeControl, time: &mut PlayerTime) {
+    match control.system {
+        TimeSystem::Fischer => time.main_time += control.period_time,
+        TimeSystem::ByoYomi | TimeSystem::Simple => time.period_time = control.period_time,
+        TimeSystem::Canadian if time.main_time <= 0.0 && control.stones > 0 => {
+            time.stones -= 1;
+            if time.stones == 0 {
+                time.period_time = control.period_time;
+                time.stones = control.stones;
+            }
+        }
+        TimeSystem::Absolute | TimeSystem::Canadian => {}
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    // Clock: Attach a wall clock with this time control, running for the player to move
+    pub fn set_time_control(&mut self, control: TimeControl) {
+        self.set_clock(GameClock::new(control));
+    }
+
+    // Clock: Remove the clock (untimed game)
+    pub fn clear_time_control(&mut self) {
+        self.clock = None;
+    }
+
+    // Clock: Milliseconds of main time left for a player (0 without a clock)
+    pub fn main_time_left(&self, player: Stone) -> f64 {
+        self.clock.as_ref().map_or(0.0, |clock| clock.time(player).main_time)
+    }
+
+    // Clock: Milliseconds left in the current overtime period or block (0 without a clock)
+    pub fn period_time_left(&self, player: Stone) -> f64 {
+        self.clock.as_ref().map_or(0.0, |clock| clock.time(player).period_time)
+    }
+
+    // Clock: Byo-yomi periods left for a player
+    pub fn periods_left(&self, player: Stone) -> u32 {
+        self.clock.as_ref().map_or(0, |clock| clock.time(player).periods)
+    }
+
+    // Clock: Stones left to play in the player's current Canadian block
+    pub fn stones_left(&self, player: Stone) -> u32 {
+        self.clock.as_ref().map_or(0, |clock| clock.time(player).stones)
+    }
+
+    // Flag Fall: End the game on time if the running side is out of time (call periodically)
+    // Returns true if the game ended on time
+    pub fn check_time(&mut self) -> bool {
+        let flagged = match self.clock.as_ref().and_then(|clock| clock.flagged()) {
+            Some(player) => player,
+            None => return false,
+        };
+        self.finish_early(GameResult::win(flagged.opponent(), ResultReason::Time));
+        true
+    }
+}
+
+impl GameState {
+    // Clock: Attach a clock (e.g. with a ManualTime source), running for the player to move
+    pub fn set_clock(&mut self, mut clock: GameClock) {
+        if !self.game_over {
+            clock.start(self.current_player);
+        }
+        self.clock = Some(clock);
+    }
+
+    pub fn clock(&self) -> Option<&GameClock> {
+        self.clock.as_ref()
+    }
+
+    // Clock: The player completed a move - switch the clock, or stop it once the game is over
+    // A move that overran the player's time loses the game on time
+    pub(crate) fn press_clock(&mut self, player: Stone) {
+        if self.phase == GamePhase::Finished {
+            return;
+        }
+        let clock = match &mut self.clock {
+            Some(clock) => clock,
+            None => return,
+        };
+        let in_time = if self.game_over { clock.stop() } else { clock.press(player) };
+        if !in_time {
+            self.finish_early(GameResult::win(player.opponent(), ResultReason::Time));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IllegalMove, Move};
+
+    // Clock: A game clock on manual time, with the handle that moves the time
+    fn manual_clock(control: TimeControl) -> (GameClock, ManualTime) {
+        let time = ManualTime::new();
+        (GameClock::with_source(control, Arc::new(time.clone())), time)
+    }
+
+    // Move: Run the player's clock for ms milliseconds, then press it
+    fn think(clock: &mut GameClock, time: &ManualTime, player: Stone, ms: f64) -> bool {
+        clock.start(player);
+        time.advance(ms);
+        clock.press(player)
+    }
+
+    #[test]
+    fn game_state_and_clock_are_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<GameClock>();
+        assert_send::<GameState>();
+    }
+
+    #[test]
+    fn absolute_time_runs_down() {
+        let (mut clock, time) = manual_clock(TimeControl::absolute(1000.0));
+        assert!(think(&mut clock, &time, Stone::Black, 400.0));
+        assert_eq!(clock.time(Stone::Black).main_time, 600.0);
+        assert_eq!(clock.time(Stone::White).main_time, 1000.0);
+        assert_eq!(clock.running(), Some(Stone::White));
+        assert!(!think(&mut clock, &time, Stone::Black, 600.5));
+        assert!(clock.time(Stone::Black).flagged);
+    }
+
+    #[test]
+    fn fischer_adds_increment_after_each_move() {
+        let (mut clock, time) = manual_clock(TimeControl::fischer(1000.0, 500.0));
+        assert!(think(&mut clock, &time, Stone::Black, 300.0));
+        assert_eq!(clock.time(Stone::Black).main_time, 1200.0);
+        assert!(think(&mut clock, &time, Stone::Black, 1100.0));
+        assert_eq!(clock.time(Stone::Black).main_time, 600.0);
+
+        // Flag: No increment for a move that overran the clock
+        assert!(!think(&mut clock, &time, Stone::Black, 700.0));
+        assert_eq!(clock.time(Stone::Black).main_time, 0.0);
+        assert!(clock.time(Stone::Black).flagged);
+    }
+
+    #[test]
+    fn byo_yomi_keeps_periods_for_moves_within_one() {
+        let (mut clock, time) = manual_clock(TimeControl::byo_yomi(1000.0, 500.0, 3));
+
+        // Main Time: Runs out 200 ms into the first period, which is renewed after the move
+        clock.start(Stone::Black);
+        time.advance(1200.0);
+        let running = clock.time(Stone::Black);
+        assert_eq!((running.main_time, running.periods, running.period_time), (0.0, 3, 300.0));
+        assert!(clock.press(Stone::Black));
+        let after = clock.time(Stone::Black);
+        assert_eq!((after.periods, after.period_time), (3, 500.0));
+
+        // Exactly One Period: Not lost
+        assert!(think(&mut clock, &time, Stone::Black, 500.0));
+        assert_eq!(clock.time(Stone::Black).periods, 3);
+    }
+
+    #[test]
+    fn byo_yomi_loses_each_period_overrun() {
+        let (mut clock, time) = manual_clock(TimeControl::byo_yomi(0.0, 500.0, 3));
+
+        // Three Periods Started: Two used up, 400 ms left in the third while the move runs
+        clock.start(Stone::Black);
+        time.advance(1100.0);
+        let running = clock.time(Stone::Black);
+        assert_eq!((running.periods, running.period_time), (1, 400.0));
+        assert!(clock.press(Stone::Black));
+        assert_eq!(clock.time(Stone::Black).periods, 1);
+
+        // Two Periods Exactly: One lost, nothing left of the second
+        let (mut clock, time) = manual_clock(TimeControl::byo_yomi(0.0, 500.0, 3));
+        clock.start(Stone::Black);
+        time.advance(1000.0);
+        let running = clock.time(Stone::Black);
+        assert_eq!((running.periods, running.period_time), (2, 0.0));
+
+        // Last Period Overrun: Flag
+        let (mut clock, time) = manual_clock(TimeControl::byo_yomi(0.0, 500.0, 3));
+        assert!(!think(&mut clock, &time, Stone::Black, 1500.5));
+        assert!(clock.time(Stone::Black).flagged);
+    }
+
+    #[test]
+    fn canadian_counts_stones_in_the_block() {
+        let (mut clock, time) = manual_clock(TimeControl::canadian(1000.0, 3000.0, 2));
+
+        // Main Time: Moves played within it do not count toward the block
+        assert!(think(&mut clock, &time, Stone::Black, 400.0));
+        assert_eq!(clock.time(Stone::Black).stones, 2);
+
+        // Block: The move that ends main time is the first stone of the block
+        assert!(think(&mut clock, &time, Stone::Black, 1100.0));
+        let black = clock.time(Stone::Black);
+        assert_eq!((black.main_time, black.period_time, black.stones), (0.0, 2500.0, 1));
+
+        // Renewal: After the last stone of the block, a full block again
+        assert!(think(&mut clock, &time, Stone::Black, 1000.0));
+        let black = clock.time(Stone::Black);
+        assert_eq!((black.period_time, black.stones), (3000.0, 2));
+
+        // Flag: Overrunning the block
+        assert!(!think(&mut clock, &time, Stone::Black, 3000.5));
+        assert!(clock.time(Stone::Black).flagged);
+    }
+
+    #[test]
+    fn simple_time_resets_every_move() {
+        let (mut clock, time) = manual_clock(TimeControl::simple(1000.0));
+        assert!(think(&mut clock, &time, Stone::Black, 900.0));
+        assert_eq!(clock.time(Stone::Black).period_time, 1000.0);
+        assert!(!think(&mut clock, &time, Stone::Black, 1000.5));
+    }
+
+    #[test]
+    fn overrunning_move_loses_on_time() {
+        let mut game = GameState::with_size(9);
+        let (clock, time) = manual_clock(TimeControl::absolute(1000.0));
+        game.set_clock(clock);
+
+        time.advance(500.0);
+        game.try_play(Move::Play((2, 2))).unwrap();
+        assert_eq!(game.main_time_left(Stone::Black), 500.0);
+        assert_eq!(game.clock().unwrap().running(), Some(Stone::White));
+
+        // Flag Fall: A move after the flag fell is refused, and the game is lost on time
+        time.advance(1200.0);
+        assert_eq!(game.try_play(Move::Play((6, 6))), Err(IllegalMove::GameOver));
+        assert!(game.game_over());
+        assert_eq!(game.result(), Some(GameResult::win(Stone::Black, ResultReason::Time)));
+        assert_eq!(game.clock().unwrap().running(), None);
+    }
+
+    #[test]
+    fn check_time_ends_the_game_on_flag_fall() {
+        let mut game = GameState::with_size(9);
+        let (clock, time) = manual_clock(TimeControl::byo_yomi(1000.0, 500.0, 2));
+        game.set_clock(clock);
+
+        time.advance(1900.0);
+        assert!(!game.check_time());
+        assert_eq!(game.periods_left(Stone::Black), 1);
+        assert_eq!(game.period_time_left(Stone::Black), 100.0);
+
+        time.advance(200.0);
+        assert!(game.check_time());
+        assert_eq!(game.result(), Some(GameResult::win(Stone::White, ResultReason::Time)));
+        assert!(!game.check_time());
+    }
+}
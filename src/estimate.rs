@@ -0,0 +1,138 @@
+// Imports: WebAssembly bindings and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::bot::playout;
+use crate::{territory_map, Bitboard, Board, GameState, Rng, Score, Stone};
+
+// Struct: Mid-game position estimate from random playouts - who owns each point, and the expected area count
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Estimate {
+    size: usize,
+    ownership: Vec<f64>, // Row-major, 1.0 always Black's .. -1.0 always White's
+    score: Score,        // Expected area for each color, komi for White
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    // Estimate: Play random games to the end from the current position (own eyes are never filled)
+    // and average who ends up owning each point; the same seed gives the same estimate
+    // A game ended by passes is played on, so dead stones still show up as the opponent's
+    pub fn estimate(&self, playouts: u32, seed: u32) -> Estimate {
+        let playouts = playouts.max(1);
+        let mut root = Bitboard::from_game(self);
+        root.play_on();
+        let mut rng = Rng::new(seed as u64);
+        let mut ownership = vec![0.0; self.size * self.size];
+        let (mut black, mut white) = (0.0, 0.0);
+        for _ in 0..playouts {
+            let mut board = root.clone();
+            playout(&mut board, self.komi, &mut rng);
+            let grid: Vec<Vec<Stone>> =
+                (0..self.size).map(|row| (0..self.size).map(|col| board.stone(row, col)).collect()).collect();
+            let territory = territory_map(&grid, self.size);
+            let points = grid.iter().flatten().zip(territory.iter().flatten());
+            for (owner, (&stone, &area)) in ownership.iter_mut().zip(points) {
+                match (stone, area) {
+                    (Stone::Black, _) | (Stone::Empty, Stone::Black) => {
+                        *owner += 1.0;
+                        black += 1.0;
+                    }
+                    (Stone::White, _) | (Stone::Empty, Stone::White) => {
+                        *owner -= 1.0;
+                        white += 1.0;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let playouts = playouts as f64;
+        ownership.iter_mut().for_each(|owner| *owner /= playouts);
+        let score = Score { black: black / playouts, white: white / playouts + self.komi };
+        Estimate { size: self.size, ownership, score }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Estimate {
+    // Ownership: Row-major ownership map (a Float64Array in JavaScript)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn ownership(&self) -> Vec<f64> {
+        self.ownership.clone()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn black(&self) -> f64 {
+        self.score.black
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn white(&self) -> f64 {
+        self.score.white
+    }
+
+    // Lead: Black's expected margin (negative when White is ahead)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn lead(&self) -> f64 {
+        self.score.black - self.score.white
+    }
+}
+
+impl Estimate {
+    // Owner: Ownership of (row, col) (0.0 off the board)
+    pub fn owner(&self, row: usize, col: usize) -> f64 {
+        if row >= self.size || col >= self.size {
+            return 0.0;
+        }
+        self.ownership[row * self.size + col]
+    }
+
+    pub fn score(&self) -> Score {
+        self.score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Settled: Black lives on the left edge and White on the right, each with three one-point eyes; only the
+    // middle column is open
+    fn settled() -> GameState {
+        let mut stones = Vec::new();
+        for row in 0..5 {
+            stones.push((row, 1, Stone::Black));
+            stones.push((row, 3, Stone::White));
+        }
+        for row in [1, 3] {
+            stones.push((row, 0, Stone::Black));
+            stones.push((row, 4, Stone::White));
+        }
+        let mut game = GameState::with_size(5);
+        game.apply_setup(&stones, Stone::Black);
+        game
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_estimate() {
+        let game = settled();
+        assert_eq!(game.estimate(20, 7), game.estimate(20, 7));
+    }
+
+    #[test]
+    fn settled_points_are_owned_outright() {
+        let estimate = settled().estimate(20, 3);
+        for row in 0..5 {
+            assert_eq!(estimate.owner(row, 0), 1.0);
+            assert_eq!(estimate.owner(row, 1), 1.0);
+            assert_eq!(estimate.owner(row, 3), -1.0);
+            assert_eq!(estimate.owner(row, 4), -1.0);
+        }
+        assert_eq!(estimate.owner(5, 0), 0.0);
+
+        // Score: Every point ends up owned, so the areas add up to the board
+        let score = estimate.score();
+        assert_eq!(score.black + score.white - settled().komi(), 25.0);
+    }
+}
@@ -0,0 +1,101 @@
+// Imports: Serialization and game types
+use serde::Serialize;
+
+use crate::{neighbors, GameState, Point, Stone};
+
+// Struct: A chain of connected stones and what touches it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Group {
+    pub color: Stone,
+    pub stones: Vec<Point>,    // In board order
+    pub liberties: Vec<Point>, // In board order
+    pub liberty_count: usize,
+    pub in_atari: bool,        // Exactly one liberty left
+    pub enemies: Vec<Point>,   // Adjacent enemy groups, each by its first stone in board order
+}
+
+impl GameState {
+    // Group: The chain at (row, col) with its liberties and adjacent enemy groups (None for an empty point)
+    pub fn group_at(&self, row: usize, col: usize) -> Option<Group> {
+        if row >= self.size || col >= self.size || self.board[row][col] == Stone::Empty {
+            return None;
+        }
+        let stones = self.chains.chain(row, col);
+        let liberties = self.chains.liberty_points(row, col);
+        let color = self.board[row][col];
+        let mut enemies: Vec<Point> = stones
+            .iter()
+            .flat_map(|&point| neighbors(self.size, point))
+            .filter(|&(r, c)| self.board[r][c] != Stone::Empty && self.board[r][c] != color)
+            .map(|(r, c)| self.chains.chain(r, c)[0])
+            .collect();
+        enemies.sort_unstable();
+        enemies.dedup();
+        let liberty_count = liberties.len();
+        Some(Group { color, stones, liberties, liberty_count, in_atari: liberty_count == 1, enemies })
+    }
+
+    // All Groups: Every chain on the board, ordered by first stone
+    pub fn all_groups(&self) -> Vec<Group> {
+        let mut seen = vec![vec![false; self.size]; self.size];
+        let mut groups = Vec::new();
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if seen[row][col] {
+                    continue;
+                }
+                if let Some(group) = self.group_at(row, col) {
+                    group.stones.iter().for_each(|&(r, c)| seen[r][c] = true);
+                    groups.push(group);
+                }
+            }
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Corner: Black's two stones at (0, 0) and (1, 0) in atari, with White to take them at (1, 1)
+    fn corner() -> GameState {
+        let stones = [(0, 0, Stone::Black), (1, 0, Stone::Black), (0, 1, Stone::White), (2, 0, Stone::White)];
+        let mut game = GameState::with_size(5);
+        game.apply_setup(&stones, Stone::White);
+        game
+    }
+
+    #[test]
+    fn group_reports_liberties_atari_and_enemies() {
+        let game = corner();
+        let black = game.group_at(1, 0).unwrap();
+        assert_eq!(black.color, Stone::Black);
+        assert_eq!(black.stones, vec![(0, 0), (1, 0)]);
+        assert_eq!(black.liberties, vec![(1, 1)]);
+        assert_eq!(black.liberty_count, 1);
+        assert!(black.in_atari);
+        assert_eq!(black.enemies, vec![(0, 1), (2, 0)]);
+
+        let white = game.group_at(0, 1).unwrap();
+        assert_eq!(white.liberties, vec![(0, 2), (1, 1)]);
+        assert!(!white.in_atari);
+        assert_eq!(white.enemies, vec![(0, 0)]);
+        assert_eq!(game.group_at(2, 2), None);
+        assert_eq!(game.group_at(5, 0), None);
+    }
+
+    #[test]
+    fn all_groups_follow_captures() {
+        let mut game = corner();
+        let firsts: Vec<Point> = game.all_groups().iter().map(|group| group.stones[0]).collect();
+        assert_eq!(firsts, vec![(0, 0), (0, 1), (2, 0)]);
+
+        assert!(game.place_stone(1, 1));
+        let groups = game.all_groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].stones, vec![(0, 1), (1, 1)]);
+        assert_eq!(groups[0].liberties, vec![(0, 0), (0, 2), (1, 0), (1, 2), (2, 1)]);
+        assert!(groups.iter().all(|group| group.color == Stone::White && group.enemies.is_empty()));
+    }
+}
@@ -0,0 +1,224 @@
+// Imports: WebAssembly bindings, serialization, and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{Chains, GameState, IllegalMove, PositionHistory, Ruleset, Stone};
+
+// Constant: Most handicap stones supported
+pub const MAX_HANDICAP: usize = 9;
+
+// Enum: Handicap stone placement - standard star points or chosen by Black
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum HandicapPlacement {
+    #[default]
+    Fixed,
+    Free,
+}
+
+// Fixed Points: Standard handicap points in GTP order (None if the size/count has no standard layout)
+// 2: lower-left, upper-right; 3-4: other corners; 5/7/9: + tengen; 6-9: side points
+pub fn fixed_handicap_points(size: usize, stones: usize) -> Option<Vec<(usize, usize)>> {
+    let odd = size % 2 == 1;
+    let max = match size {
+        s if s < 7 => 0,
+        _ if odd => MAX_HANDICAP,
+        _ => 4,
+    };
+    if !(2..=max).contains(&stones) {
+        return None;
+    }
+
+    let lo = if size >= 13 { 3 } else { 2 };
+    let hi = size - 1 - lo;
+    let mid = size / 2;
+    let corners = [(hi, lo), (lo, hi), (lo, lo), (hi, hi)];
+    let sides = [(mid, lo), (mid, hi), (hi, mid), (lo, mid)];
+
+    let mut points: Vec<(usize, usize)> = corners.iter().take(stones.min(4)).copied().collect();
+    let side_count = match stones {
+        6 | 7 => 2,
+        8 | 9 => 4,
+        _ => 0,
+    };
+    points.extend(sides.iter().take(side_count));
+    if stones >= 5 && stones % 2 == 1 {
+        points.push((mid, mid));
+    }
+    Some(points)
+}
+
+impl Ruleset {
+    // Komi: Compensation for White in a handicap game
+    // Area-scoring rulesets give White extra points per handicap stone (AGA: one fewer)
+    pub fn handicap_komi(&self, stones: usize) -> f64 {
+        let compensation = match self {
+            Ruleset::Japanese | Ruleset::TrompTaylor => 0,
+            Ruleset::Chinese | Ruleset::NewZealand | Ruleset::Ing => stones,
+            Ruleset::Aga => stones.saturating_sub(1),
+        };
+        0.5 + compensation as f64
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    // Handicap: Start a new handicap game (same size, ruleset, ko rule) with White to move after the stones
+    // Fixed places standard points now; Free lets Black place the stones with place_stone first
+    // 1 stone means no komi and no stones; returns false if no fixed layout exists for this size/count
+    pub fn new_handicap(&mut self, stones: usize, placement: HandicapPlacement) -> bool {
+        if stones > MAX_HANDICAP || stones > self.size * self.size {
+            return false;
+        }
+        let points = match placement {
+            HandicapPlacement::Fixed if stones >= 2 => match fixed_handicap_points(self.size, stones) {
+                Some(points) => points,
+                None => return false,
+            },
+            _ => Vec::new(),
+        };
+
+        let ko_rule = self.ko_rule;
+        *self = GameState::with_rules(self.size, self.ruleset);
+        self.ko_rule = ko_rule;
+        self.handicap = stones;
+        self.handicap_placement = placement;
+        if stones == 0 {
+            return true;
+        }
+
+        self.komi = self.ruleset.handicap_komi(stones);
+        if stones == 1 {
+            return true;
+        }
+        match placement {
+            HandicapPlacement::Fixed => {
+                let setup: Vec<(usize, usize, Stone)> =
+                    points.into_iter().map(|(row, col)| (row, col, Stone::Black)).collect();
+                self.apply_setup(&setup, Stone::White);
+            }
+            HandicapPlacement::Free => self.handicap_to_place = stones,
+        }
+        true
+    }
+
+    // Getters: Handicap placement and free-placement stones Black still has to place
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn handicap_placement(&self) -> HandicapPlacement {
+        self.handicap_placement
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn handicap_to_place(&self) -> usize {
+        self.handicap_to_place
+    }
+}
+
+impl GameState {
+    // Free Handicap: Put one of Black's handicap stones on an empty point (setup, not a move)
+    pub(crate) fn place_handicap_stone(&mut self, row: usize, col: usize) -> Result<(), IllegalMove> {
+        if row >= self.size || col >= self.size {
+            return Err(IllegalMove::OutOfBounds);
+        }
+        if self.board[row][col] != Stone::Empty {
+            return Err(IllegalMove::Occupied);
+        }
+        self.board[row][col] = Stone::Black;
+        self.chains = Chains::from_board(&self.board);
+        self.handicap_to_place -= 1;
+        let player = if self.handicap_to_place == 0 { Stone::White } else { Stone::Black };
+        self.current_player = player;
+        self.position_history = PositionHistory::new(self.chains.hash(), player);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Move;
+
+    #[test]
+    fn fixed_points_follow_gtp_order() {
+        assert_eq!(fixed_handicap_points(9, 5), Some(vec![(6, 2), (2, 6), (2, 2), (6, 6), (4, 4)]));
+        assert_eq!(fixed_handicap_points(13, 4), Some(vec![(9, 3), (3, 9), (3, 3), (9, 9)]));
+        assert_eq!(fixed_handicap_points(19, 2), Some(vec![(15, 3), (3, 15)]));
+        assert_eq!(fixed_handicap_points(19, 6), Some(vec![(15, 3), (3, 15), (3, 3), (15, 15), (9, 3), (9, 15)]));
+        let nine = vec![(15, 3), (3, 15), (3, 3), (15, 15), (9, 3), (9, 15), (15, 9), (3, 9), (9, 9)];
+        assert_eq!(fixed_handicap_points(19, 9), Some(nine));
+
+        // None: One stone, too many, too small a board, or an odd count past 4 on an even board
+        assert_eq!(fixed_handicap_points(19, 1), None);
+        assert_eq!(fixed_handicap_points(19, 10), None);
+        assert_eq!(fixed_handicap_points(5, 2), None);
+        assert_eq!(fixed_handicap_points(10, 5), None);
+    }
+
+    #[test]
+    fn fixed_handicap_sets_stones_komi_and_white_to_move() {
+        let komi = [
+            (Ruleset::Japanese, 0.5),
+            (Ruleset::TrompTaylor, 0.5),
+            (Ruleset::Chinese, 4.5),
+            (Ruleset::NewZealand, 4.5),
+            (Ruleset::Ing, 4.5),
+            (Ruleset::Aga, 3.5),
+        ];
+        for (ruleset, komi) in komi {
+            let mut game = GameState::with_rules(19, ruleset);
+            assert!(game.new_handicap(4, HandicapPlacement::Fixed));
+            assert_eq!(game.komi(), komi, "{:?}", ruleset);
+            assert_eq!(game.current_player(), Stone::White);
+            assert_eq!(game.move_number(), 0);
+            for (row, col) in fixed_handicap_points(19, 4).unwrap() {
+                assert_eq!(game.get_stone(row, col), Stone::Black);
+            }
+            assert_eq!(game.board().iter().flatten().filter(|&&s| s == Stone::Black).count(), 4);
+        }
+
+        // One Stone: No stones and half a point of komi, with Black still moving first
+        let mut game = GameState::with_rules(19, Ruleset::Japanese);
+        assert!(game.new_handicap(1, HandicapPlacement::Fixed));
+        assert_eq!(game.komi(), 0.5);
+        assert_eq!(game.current_player(), Stone::Black);
+        assert!(game.board().iter().flatten().all(|&stone| stone == Stone::Empty));
+    }
+
+    #[test]
+    fn free_handicap_waits_for_black_to_place_the_stones() {
+        let mut game = GameState::with_rules(9, Ruleset::Chinese);
+        assert!(game.new_handicap(3, HandicapPlacement::Free));
+        assert_eq!(game.handicap_to_place(), 3);
+        assert_eq!(game.current_player(), Stone::Black);
+        assert_eq!(game.try_play(Move::Pass), Err(IllegalMove::HandicapPending));
+
+        assert!(game.place_stone(2, 2));
+        assert_eq!(game.check_move(2, 2), Err(IllegalMove::Occupied));
+        assert!(game.place_stone(6, 6));
+        assert!(game.place_stone(4, 4));
+        assert_eq!(game.handicap_to_place(), 0);
+        assert_eq!(game.current_player(), Stone::White);
+        assert_eq!(game.move_number(), 0);
+        assert_eq!(game.komi(), 3.5);
+    }
+
+    #[test]
+    fn reset_clears_loaded_games_without_a_fixed_layout() {
+        // Free: Five stones have no fixed layout on an even board, so Black places them again
+        let mut game = GameState::from_sgf("(;FF[4]SZ[10]HA[5]AB[cc][hh][ch][hc][ee];W[dd];B[gg])").unwrap();
+        assert_eq!(game.move_number(), 2);
+        game.reset();
+        assert_eq!(game.move_number(), 0);
+        assert_eq!(game.move_count(), 0);
+        assert!(game.board().iter().flatten().all(|&stone| stone == Stone::Empty));
+        assert_eq!(game.handicap_to_place(), 5);
+
+        // None: More stones than any placement allows
+        let mut game = GameState::from_sgf("(;FF[4]SZ[19]HA[12];W[dd];B[pp])").unwrap();
+        game.reset();
+        assert_eq!(game.move_number(), 0);
+        assert!(game.board().iter().flatten().all(|&stone| stone == Stone::Empty));
+        assert_eq!(game.current_player(), Stone::Black);
+    }
+}
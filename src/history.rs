@@ -0,0 +1,267 @@
+// Imports: WebAssembly bindings, serialization, and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+
+use crate::{zobrist, Chains, GameState, Move, Stone};
+
+// Struct: One recorded move - who played, where (None = pass), what it removed, prisoners after it, comment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MoveRecord {
+    pub player: Stone,
+    pub point: Option<(usize, usize)>,
+    pub captured: Vec<(usize, usize)>, // Opponent stones removed by the move
+    pub suicided: Vec<(usize, usize)>, // Own stones removed by the move (suicide rulesets)
+    pub black_captured: usize,
+    pub white_captured: usize,
+    pub comment: Option<String>,
+}
+
+impl MoveRecord {
+    // Revert: Restore a board to how it was before this move
+    pub(crate) fn unapply(&self, board: &mut [Vec<Stone>]) {
+        let opponent = self.player.opponent();
+        for &(r, c) in &self.suicided {
+            board[r][c] = self.player;
+        }
+        for &(r, c) in &self.captured {
+            board[r][c] = opponent;
+        }
+        if let Some((r, c)) = self.point {
+            board[r][c] = Stone::Empty;
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    // Undo: Take back the last move, restoring board, prisoners, ko state and passes
+    pub fn undo(&mut self) -> bool {
+        if self.move_number == 0 {
+            return false;
+        }
+
+        self.move_number -= 1;
+        let record = self.moves[self.move_number].clone();
+        record.unapply(&mut self.board);
+        self.chains = Chains::from_board(&self.board);
+        self.position_history.pop();
+        self.current_player = record.player;
+        self.game_over = false;
+        self.clear_scoring();
+
+        // Prisoners: Counts as recorded after the preceding move
+        let (black_captured, white_captured) = match self.move_number {
+            0 => (0, 0),
+            n => (self.moves[n - 1].black_captured, self.moves[n - 1].white_captured),
+        };
+        self.black_captured = black_captured;
+        self.white_captured = white_captured;
+
+        // Ko: Previous position is the one before the preceding move
+        self.previous_hash = match self.move_number {
+            0 => None,
+            n => {
+                let mut board = self.board.clone();
+                self.moves[n - 1].unapply(&mut board);
+                Some(zobrist::hash_board(&board))
+            }
+        };
+
+        // Passes: Count trailing passes and restore the last stone played
+        let played = &self.moves[..self.move_number];
+        self.consecutive_passes = played.iter().rev().take_while(|m| m.point.is_none()).count();
+        self.last_move = played.last().and_then(|m| m.point);
+
+        // Clock: Runs for the player to move again (time already used is not given back)
+        if let Some(clock) = &mut self.clock {
+            clock.start(self.current_player);
+        }
+
+        true
+    }
+
+    // Redo: Replay the next move from the record (kept until a different move is played)
+    pub fn redo(&mut self) -> bool {
+        if self.move_number >= self.moves.len() {
+            return false;
+        }
+
+        // Future: Kept whole if the move cannot be replayed (e.g. the clock has run out)
+        let mut future = self.moves.split_off(self.move_number);
+        let record = future.remove(0);
+        let replayed = self.replay(&record);
+        if !replayed {
+            future.insert(0, record);
+        }
+        self.moves.extend(future);
+        replayed
+    }
+
+    // Go To: Undo or redo until the position after move n (clamped to the record)
+    pub fn goto_move(&mut self, n: usize) {
+        while self.move_number > n && self.undo() {}
+        while self.move_number < n && self.redo() {}
+    }
+
+    // Count: Total moves in the record, including undone moves available to redo
+    pub fn move_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    // Move Number: Moves played up to the current position
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn move_number(&self) -> usize {
+        self.move_number
+    }
+
+    // Comment: Get the comment on the current move (or the game comment before the first move)
+    pub fn get_comment(&self) -> Option<String> {
+        match self.move_number {
+            0 => self.comment.clone(),
+            n => self.moves[n - 1].comment.clone(),
+        }
+    }
+
+    // Comment: Set the comment on the current move (or the game comment before the first move)
+    pub fn set_comment(&mut self, text: &str) {
+        let comment = Some(text.to_string()).filter(|t| !t.is_empty());
+        match self.move_number {
+            0 => self.comment = comment,
+            n => self.moves[n - 1].comment = comment,
+        }
+    }
+}
+
+impl GameState {
+    // History: Recorded moves (including undone moves available to redo)
+    pub fn moves(&self) -> &[MoveRecord] {
+        &self.moves
+    }
+
+    // Play As: Play a stone (or pass for None) as the given color, resuming play after passes
+    pub fn play_as(&mut self, player: Stone, point: Option<(usize, usize)>) -> bool {
+        self.current_player = player;
+        self.game_over = false;
+        self.clear_scoring();
+        match point {
+            Some((row, col)) => self.place_stone(row, col),
+            None => self.try_play(Move::Pass).is_ok(),
+        }
+    }
+
+    // Replay: Play a recorded move again, keeping its comment
+    pub(crate) fn replay(&mut self, record: &MoveRecord) -> bool {
+        let replayed = self.play_as(record.player, record.point);
+        if replayed {
+            self.moves[self.move_number - 1].comment = record.comment.clone();
+        }
+        replayed
+    }
+
+    // Record: Append a move at the current position, discarding any undone moves
+    pub(crate) fn record_move(&mut self, record: MoveRecord) {
+        self.moves.truncate(self.move_number);
+        self.moves.push(record);
+        self.move_number += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameClock, GamePhase, IllegalMove, ManualTime, TimeControl};
+    use std::sync::Arc;
+
+    // Ko: White's stone at (2, 2) is taken from (2, 3) on move 9, and White may not take back at once
+    fn ko_game() -> GameState {
+        let mut game = GameState::with_size(9);
+        for point in [(1, 2), (1, 3), (2, 1), (2, 4), (3, 2), (3, 3), (7, 7), (2, 2), (2, 3)] {
+            assert!(game.place_stone(point.0, point.1));
+        }
+        game
+    }
+
+    #[test]
+    fn undo_and_redo_restore_captures_prisoners_and_ko() {
+        let mut game = ko_game();
+        assert_eq!(game.black_captured(), 1);
+        assert_eq!(game.check_move(2, 2), Err(IllegalMove::Ko));
+
+        assert!(game.undo());
+        assert_eq!(game.get_stone(2, 2), Stone::White);
+        assert_eq!(game.get_stone(2, 3), Stone::Empty);
+        assert_eq!(game.black_captured(), 0);
+        assert_eq!(game.current_player(), Stone::Black);
+        assert_eq!(game.last_move(), Some((2, 2)));
+
+        assert!(game.redo());
+        assert_eq!(game.get_stone(2, 2), Stone::Empty);
+        assert_eq!(game.get_stone(2, 3), Stone::Black);
+        assert_eq!(game.black_captured(), 1);
+        assert_eq!(game.check_move(2, 2), Err(IllegalMove::Ko));
+        assert!(!game.redo());
+
+        // Ko: The ban lifts after a move elsewhere and returns when that move is undone
+        assert!(game.place_stone(7, 1));
+        assert!(game.place_stone(6, 6));
+        assert_eq!(game.check_move(2, 2), Ok(()));
+        game.goto_move(9);
+        assert_eq!(game.current_player(), Stone::White);
+        assert_eq!(game.check_move(2, 2), Err(IllegalMove::Ko));
+        assert_eq!(game.move_count(), 11);
+    }
+
+    #[test]
+    fn goto_move_walks_the_record_both_ways() {
+        let mut game = ko_game();
+        game.goto_move(0);
+        assert_eq!(game.move_number(), 0);
+        assert!(game.board().iter().flatten().all(|&stone| stone == Stone::Empty));
+        assert_eq!(game.black_captured(), 0);
+        assert_eq!(game.last_move(), None);
+
+        game.goto_move(100);
+        assert_eq!(game.move_number(), 9);
+        assert_eq!(game.board(), ko_game().board());
+        assert_eq!(game.black_captured(), 1);
+        assert_eq!(game.check_move(2, 2), Err(IllegalMove::Ko));
+    }
+
+    #[test]
+    fn undoing_a_pass_leaves_scoring_and_redo_passes_again() {
+        let mut game = ko_game();
+        game.pass();
+        game.pass();
+        assert_eq!(game.phase(), GamePhase::Scoring);
+        assert!(game.game_over());
+
+        assert!(game.undo());
+        assert_eq!(game.phase(), GamePhase::Playing);
+        assert!(!game.game_over());
+        assert_eq!(game.last_move(), None);
+        assert_eq!(game.current_player(), Stone::Black);
+
+        // Passes: One pass is still counted, so redoing the second ends the game again
+        assert!(game.redo());
+        assert_eq!(game.phase(), GamePhase::Scoring);
+        assert_eq!(game.move_number(), 11);
+        assert_eq!(game.black_captured(), 1);
+    }
+
+    #[test]
+    fn failed_redo_keeps_the_record() {
+        let time = ManualTime::new();
+        let mut game = GameState::with_size(9);
+        game.set_clock(GameClock::with_source(TimeControl::absolute(1000.0), Arc::new(time.clone())));
+        assert!(game.place_stone(2, 2));
+        assert!(game.place_stone(6, 6));
+        assert!(game.undo());
+
+        // Flag: Black's time runs out, so White's move cannot be replayed
+        time.advance(2000.0);
+        assert!(!game.redo());
+        assert_eq!(game.move_count(), 2);
+        assert_eq!(game.moves()[1].point, Some((6, 6)));
+    }
+}
@@ -0,0 +1,192 @@
+// Imports: WebAssembly bindings and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::chains::neighbors;
+use crate::{GameState, Point, Stone};
+
+// Constants: Bouzy's dilation/erosion counts for territory, moyo (frameworks) and area (sphere of influence)
+const TERRITORY: (usize, usize) = (5, 21);
+const MOYO: (usize, usize) = (5, 10);
+const AREA: (usize, usize) = (4, 0);
+
+// Constant: Starting value of a stone (positive for Black, negative for White)
+const STONE_VALUE: i32 = 128;
+
+// Struct: Influence of the stones on the board, from Bouzy's dilation/erosion operators
+// Each map holds the owning color per point, row-major
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Influence {
+    size: usize,
+    board: Vec<Stone>,
+    territory: Vec<Stone>, // Points a color already all but owns
+    moyo: Vec<Stone>,      // Frameworks a color is building
+    area: Vec<Stone>,      // Where a color's influence reaches
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    // Influence: Territory, moyo and area of each color from the stones on the board (dead stones included)
+    pub fn influence(&self) -> Influence {
+        let board: Vec<Stone> = self.board.iter().flatten().copied().collect();
+        let map = |(dilations, erosions)| owners(&bouzy(self.size, &board, dilations, erosions));
+        Influence { size: self.size, territory: map(TERRITORY), moyo: map(MOYO), area: map(AREA), board }
+    }
+}
+
+impl Influence {
+    // Territory: Empty points inside the player's territory, in board order
+    pub fn territory(&self, player: Stone) -> Vec<Point> {
+        self.empty_points(|point| self.territory[point] == player)
+    }
+
+    // Moyo: Empty points of the player's frameworks outside its territory, in board order
+    pub fn moyo(&self, player: Stone) -> Vec<Point> {
+        self.empty_points(|point| self.moyo[point] == player && self.territory[point] != player)
+    }
+
+    // Contested: Empty points neither color's area reaches, lying between the two (next to both areas)
+    pub fn contested(&self) -> Vec<Point> {
+        self.empty_points(|point| self.is_contested(point))
+    }
+
+    fn is_contested(&self, point: usize) -> bool {
+        if self.area[point] != Stone::Empty {
+            return false;
+        }
+        let near = |player| neighbors(self.size, point).any(|n| self.area[n] == player);
+        near(Stone::Black) && near(Stone::White)
+    }
+
+    fn empty_points(&self, keep: impl Fn(usize) -> bool) -> Vec<Point> {
+        (0..self.size * self.size)
+            .filter(|&point| self.board[point] == Stone::Empty && keep(point))
+            .map(|point| (point / self.size, point % self.size))
+            .collect()
+    }
+
+    // Codes: A map as stone codes (0 none, 1 Black, 2 White)
+    fn codes(map: &[Stone]) -> Vec<u8> {
+        map.iter().map(Stone::code).collect()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Influence {
+    // Maps: Row-major owner codes (0 none, 1 Black, 2 White; a Uint8Array in JavaScript)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn territory_codes(&self) -> Vec<u8> {
+        Influence::codes(&self.territory)
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn moyo_codes(&self) -> Vec<u8> {
+        Influence::codes(&self.moyo)
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn area_codes(&self) -> Vec<u8> {
+        Influence::codes(&self.area)
+    }
+
+    // Contested: Row-major flags (1 contested, 0 not) for empty points between the two colors' areas
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn contested_codes(&self) -> Vec<u8> {
+        (0..self.size * self.size)
+            .map(|point| u8::from(self.board[point] == Stone::Empty && self.is_contested(point)))
+            .collect()
+    }
+}
+
+// Bouzy: Start from the stones, then dilate (spread each color into points no opposing value touches)
+// and erode (shrink each color where it borders zero or the other color)
+fn bouzy(size: usize, board: &[Stone], dilations: usize, erosions: usize) -> Vec<i32> {
+    let mut values: Vec<i32> = board
+        .iter()
+        .map(|stone| match stone {
+            Stone::Black => STONE_VALUE,
+            Stone::White => -STONE_VALUE,
+            Stone::Empty => 0,
+        })
+        .collect();
+
+    for _ in 0..dilations {
+        values = (0..values.len())
+            .map(|point| {
+                let value = values[point];
+                let near = neighbors(size, point).map(|n| values[n]);
+                let (positive, negative) = near.fold((0, 0), |(p, n), v| (p + i32::from(v > 0), n + i32::from(v < 0)));
+                match value {
+                    v if v >= 0 && negative == 0 => v + positive,
+                    v if v <= 0 && positive == 0 => v - negative,
+                    v => v,
+                }
+            })
+            .collect();
+    }
+
+    for _ in 0..erosions {
+        values = (0..values.len())
+            .map(|point| {
+                let value = values[point];
+                let near = neighbors(size, point).map(|n| values[n]);
+                match value {
+                    v if v > 0 => (v - near.filter(|&n| n <= 0).count() as i32).max(0),
+                    v if v < 0 => (v + near.filter(|&n| n >= 0).count() as i32).min(0),
+                    _ => 0,
+                }
+            })
+            .collect();
+    }
+    values
+}
+
+// Owners: Color of each point's value (positive Black, negative White)
+fn owners(values: &[i32]) -> Vec<Stone> {
+    values
+        .iter()
+        .map(|&value| match value {
+            v if v > 0 => Stone::Black,
+            v if v < 0 => Stone::White,
+            _ => Stone::Empty,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Position: A 9x9 board with the given Black and White stones
+    fn position(black: &[Point], white: &[Point]) -> GameState {
+        let mut stones: Vec<(usize, usize, Stone)> = black.iter().map(|&(r, c)| (r, c, Stone::Black)).collect();
+        stones.extend(white.iter().map(|&(r, c)| (r, c, Stone::White)));
+        let mut game = GameState::with_size(9);
+        game.apply_setup(&stones, Stone::Black);
+        game
+    }
+
+    #[test]
+    fn lone_stone_builds_a_moyo_but_no_territory() {
+        let influence = position(&[(4, 4)], &[]).influence();
+        assert!(influence.territory(Stone::Black).is_empty());
+        let moyo = influence.moyo(Stone::Black);
+        assert!(moyo.contains(&(4, 5)) && moyo.contains(&(3, 4)));
+        assert!(!moyo.contains(&(4, 4)));
+        assert!(influence.moyo(Stone::White).is_empty());
+        assert!(influence.contested().is_empty());
+    }
+
+    #[test]
+    fn facing_stones_contest_the_points_between_them() {
+        let influence = position(&[(4, 2)], &[(4, 6)]).influence();
+        let contested = influence.contested();
+        assert!(contested.contains(&(4, 4)), "{:?}", contested);
+        assert!(contested.iter().all(|&(_, col)| col == 4));
+        assert!(influence.moyo(Stone::Black).contains(&(3, 2)));
+        assert!(influence.moyo(Stone::White).contains(&(3, 6)));
+        let codes = influence.contested_codes();
+        assert_eq!(codes.iter().filter(|&&code| code == 1).count(), contested.len());
+    }
+}
@@ -0,0 +1,188 @@
+// Imports: Serialization and game types
+use serde::Serialize;
+
+use crate::{Chains, GameState, Point, Stone};
+
+// Constant: Positions read before a ladder is given up as unreadable (and counted as not working)
+const READ_LIMIT: usize = 20_000;
+
+// Struct: Result of reading a ladder (shicho) against a chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Ladder {
+    pub works: bool,       // The chain is captured whatever it does
+    pub moves: Vec<Point>, // Main line, alternating colors; ends with the capture if it works
+}
+
+// Struct: Ladder reader - the chain's owner defends, the other color attacks, ko is ignored
+struct Reader {
+    defender: Stone,
+    attacker: Stone,
+    budget: usize,
+}
+
+impl GameState {
+    // Ladder: Read out the ladder against the chain at (row, col), including ladder breakers
+    // A chain in atari moves first (it tries to run or capture its way out); with two liberties the attacker
+    // moves first. None for an empty point or a chain with more liberties
+    pub fn ladder_status(&self, row: usize, col: usize) -> Option<Ladder> {
+        if row >= self.size || col >= self.size {
+            return None;
+        }
+        let mut reader = Reader::new(self.board[row][col])?;
+        let mut moves = Vec::new();
+        let works = match self.chains.liberties(row, col) {
+            1 => !reader.escape(&self.chains, (row, col), &mut moves),
+            2 => reader.capture(&self.chains, (row, col), &mut moves),
+            _ => return None,
+        };
+        Some(Ladder { works, moves })
+    }
+}
+
+// Ladder Works: Whether the attacker, to move, captures the two-liberty chain at target in a ladder
+pub(crate) fn ladder_works(chains: &Chains, target: Point, defender: Stone) -> bool {
+    Reader::new(defender).is_some_and(|mut reader| reader.capture(chains, target, &mut Vec::new()))
+}
+
+impl Reader {
+    fn new(defender: Stone) -> Option<Reader> {
+        if defender == Stone::Empty {
+            return None;
+        }
+        let attacker = defender.opponent();
+        Some(Reader { defender, attacker, budget: READ_LIMIT })
+    }
+
+    // Capture: Attacker to move against a chain with two liberties - true if an atari on either liberty
+    // leaves it unable to escape; line gets the capturing sequence, or the longest failed try
+    fn capture(&mut self, chains: &Chains, target: Point, line: &mut Vec<Point>) -> bool {
+        let mut failed = None;
+        for (row, col) in chains.liberty_points(target.0, target.1) {
+            if self.budget == 0 {
+                break;
+            }
+            self.budget -= 1;
+            if chains.probe(row, col, self.attacker).suicide {
+                continue;
+            }
+            let mut next = chains.clone();
+            next.play(row, col, self.attacker);
+            let mut reply = vec![(row, col)];
+            if next.liberties(target.0, target.1) == 1 && !self.escape(&next, target, &mut reply) {
+                line.extend(reply);
+                return true;
+            }
+            keep_longest(&mut failed, reply);
+        }
+        line.extend(failed.unwrap_or_default());
+        false
+    }
+
+    // Escape: Defender to move with the chain in atari - captures an adjacent attacker chain in atari
+    // (a ladder breaker or a weak chaser) or extends; true if the chain ends up with three or more
+    // liberties, or survives an attacker that cannot put it back in atari
+    fn escape(&mut self, chains: &Chains, target: Point, line: &mut Vec<Point>) -> bool {
+        let liberties = chains.liberty_points(target.0, target.1);
+        let mut defenses = chains.capturing_points(target.0, target.1);
+        if !defenses.contains(&liberties[0]) {
+            defenses.push(liberties[0]);
+        }
+
+        let mut failed = None;
+        for (row, col) in defenses {
+            if self.budget == 0 {
+                // Unreadable: Give the defender the benefit of the doubt
+                line.push((row, col));
+                return true;
+            }
+            self.budget -= 1;
+            if chains.probe(row, col, self.defender).suicide {
+                continue;
+            }
+            let mut next = chains.clone();
+            next.play(row, col, self.defender);
+            let mut reply = vec![(row, col)];
+            let escaped = match next.liberties(target.0, target.1) {
+                0 | 1 => {
+                    reply.extend(next.liberty_points(target.0, target.1));
+                    false
+                }
+                2 => !self.capture(&next, target, &mut reply),
+                _ => true,
+            };
+            if escaped {
+                line.extend(reply);
+                return true;
+            }
+            keep_longest(&mut failed, reply);
+        }
+
+        // Captured: Every defense fails, or there was none and the attacker simply takes the chain
+        line.extend(failed.unwrap_or(liberties));
+        false
+    }
+}
+
+// Longest: Keep the longer of two failed lines, so the main line shows the most stubborn try
+fn keep_longest(failed: &mut Option<Vec<Point>>, reply: Vec<Point>) {
+    if failed.as_ref().is_none_or(|line| reply.len() > line.len()) {
+        *failed = Some(reply);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Position: A 9x9 board with the given Black and White stones
+    fn position(black: &[Point], white: &[Point]) -> GameState {
+        let mut stones: Vec<(usize, usize, Stone)> = black.iter().map(|&(r, c)| (r, c, Stone::Black)).collect();
+        stones.extend(white.iter().map(|&(r, c)| (r, c, Stone::White)));
+        let mut game = GameState::with_size(9);
+        game.apply_setup(&stones, Stone::Black);
+        game
+    }
+
+    // Ladder: White's stone at (4, 4) has two liberties, and either atari chases it toward a corner
+    const CHASERS: [Point; 3] = [(3, 4), (4, 3), (5, 5)];
+
+    #[test]
+    fn ladder_runs_to_the_edge() {
+        let game = position(&CHASERS, &[(4, 4)]);
+        let ladder = game.ladder_status(4, 4).unwrap();
+        assert!(ladder.works);
+        assert_eq!(ladder.moves.len() % 2, 1);
+        let &(row, col) = ladder.moves.last().unwrap();
+        assert!(row == 8 || col == 8, "captured at ({}, {})", row, col);
+        assert!(ladder_works(&game.chains, (4, 4), Stone::White));
+    }
+
+    #[test]
+    fn breakers_on_the_diagonals_stop_the_ladder() {
+        // One Breaker: The attacker chases the other way
+        let game = position(&CHASERS, &[(4, 4), (6, 2)]);
+        assert!(game.ladder_status(4, 4).unwrap().works);
+
+        let game = position(&CHASERS, &[(4, 4), (6, 2), (2, 6)]);
+        assert!(!game.ladder_status(4, 4).unwrap().works);
+        assert!(!ladder_works(&game.chains, (4, 4), Stone::White));
+    }
+
+    #[test]
+    fn defender_escapes_by_capturing_a_chaser() {
+        // Atari: White's stone has one liberty, but Black's chaser at (4, 5) can be taken at (4, 6)
+        let game = position(&[(3, 4), (4, 3), (4, 5)], &[(4, 4), (3, 5), (5, 5)]);
+        let ladder = game.ladder_status(4, 4).unwrap();
+        assert!(!ladder.works);
+        assert_eq!(ladder.moves[0], (4, 6));
+    }
+
+    #[test]
+    fn no_ladder_for_empty_points_or_free_chains() {
+        let game = position(&CHASERS, &[(4, 4), (1, 1)]);
+        assert_eq!(game.ladder_status(0, 0), None);
+        assert_eq!(game.ladder_status(1, 1), None);
+        assert_eq!(game.ladder_status(9, 0), None);
+        assert!(!ladder_works(&game.chains, (0, 0), Stone::Empty));
+    }
+}
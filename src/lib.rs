@@ -1,599 +1,692 @@
 // Imports: WebAssembly bindings, serialization, and data structures
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashSet, VecDeque};
 
-// Constant: Standard Go board size (19x19)
-const BOARD_SIZE: usize = 19;
+use chains::{Chains, Probe};
+use ruleset::PositionHistory;
+
+mod benson;
+mod bitboard;
+mod board;
+mod bot;
+mod chains;
+mod clock;
+mod estimate;
+mod groups;
+mod handicap;
+mod influence;
+mod history;
+mod ladder;
+mod moves;
+mod player;
+mod result;
+mod ruleset;
+mod scoring;
+mod sgf;
+mod tree;
+mod tsumego;
+mod warnings;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod zobrist;
+pub use benson::PassAlive;
+pub use bitboard::Bitboard;
+pub use board::Board;
+pub use bot::{Mcts, Rng};
+pub use clock::{GameClock, ManualTime, PlayerTime, SystemTime, TimeControl, TimeSource, TimeSystem};
+pub use estimate::Estimate;
+pub use groups::Group;
+pub use handicap::{fixed_handicap_points, HandicapPlacement, MAX_HANDICAP};
+pub use history::MoveRecord;
+pub use influence::Influence;
+pub use ladder::Ladder;
+pub use moves::{IllegalMove, Move, MoveOutcome};
+pub use player::{Bot, Difficulty, GreedyPlayer, PatternPlayer, Player, RandomPlayer};
+pub use result::{GameResult, ResultReason};
+pub use ruleset::{KoRule, Ruleset, ScoringMethod};
+pub use scoring::{GamePhase, Score};
+pub use sgf::SgfError;
+pub use tree::{GameTree, TreeNode};
+pub use tsumego::{LifeSolution, LifeStatus};
+pub use warnings::MoveWarnings;
+
+// Constants: Standard Go board size (19x19) and supported size range
+pub const DEFAULT_BOARD_SIZE: usize = 19;
+pub const MIN_BOARD_SIZE: usize = 2;
+pub const MAX_BOARD_SIZE: usize = 25;
 
 // Enum: Stone types (Empty, Black, White) - exported to JavaScript
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum Stone {
     Empty,
     Black,
     White,
 }
 
-// Struct: Board position (row, col) with helper methods
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Position {
-    row: usize,
-    col: usize,
-}
-
-impl Position {
-    // Create: New position
-    fn new(row: usize, col: usize) -> Self {
-        Position { row, col }
+impl Stone {
+    // Code: Numeric form used by the JavaScript board (0 empty, 1 black, 2 white)
+    pub fn code(&self) -> u8 {
+        match self {
+            Stone::Empty => 0,
+            Stone::Black => 1,
+            Stone::White => 2,
+        }
     }
 
-    // Neighbors: Get adjacent positions (up, down, left, right)
-    fn neighbors(&self) -> Vec<Position> {
-        let mut neighbors = Vec::new();
-        if self.row > 0 {
-            neighbors.push(Position::new(self.row - 1, self.col));
-        }
-        if self.row < BOARD_SIZE - 1 {
-            neighbors.push(Position::new(self.row + 1, self.col));
-        }
-        if self.col > 0 {
-            neighbors.push(Position::new(self.row, self.col - 1));
+    // Opponent: The other color (Empty has none and stays Empty)
+    pub fn opponent(&self) -> Stone {
+        match self {
+            Stone::Empty => Stone::Empty,
+            Stone::Black => Stone::White,
+            Stone::White => Stone::Black,
         }
-        if self.col < BOARD_SIZE - 1 {
-            neighbors.push(Position::new(self.row, self.col + 1));
+    }
+}
+
+// Type: Board point as (row, col), row 0 at the top
+pub type Point = (usize, usize);
+
+// Struct: One intersection of a board view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Intersection {
+    pub row: usize,
+    pub col: usize,
+    pub stone: u8, // Stone::code
+    pub is_star_point: bool,
+    pub is_valid_move: bool,
+    pub is_last_move: bool,
+    pub is_dead: bool,
+    pub is_seki: bool,
+    pub territory: u8, // Owner of the point after the game (0 none, 1 black, 2 white)
+}
+
+// Struct: Everything needed to draw the board, row by row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BoardView {
+    pub size: usize,
+    pub intersections: Vec<Intersection>,
+}
+
+// Neighbors: Adjacent points (up, down, left, right) on a board of given size
+fn neighbors(size: usize, (row, col): Point) -> impl Iterator<Item = Point> {
+    chains::neighbors(size, row * size + col).map(move |point| (point / size, point % size))
+}
+
+// Territory: Owner of each point - Black/White for empty regions bordered by one color only, else Empty
+fn territory_map(board: &[Vec<Stone>], size: usize) -> Vec<Vec<Stone>> {
+    let mut owners = vec![vec![Stone::Empty; size]; size];
+    let mut visited = HashSet::new();
+
+    for row in 0..size {
+        for col in 0..size {
+            if board[row][col] != Stone::Empty || visited.contains(&(row, col)) {
+                continue;
+            }
+            let mut region = vec![(row, col)];
+            let mut queue = VecDeque::new();
+            queue.push_back((row, col));
+            visited.insert((row, col));
+
+            let mut has_black = false;
+            let mut has_white = false;
+
+            // Flood fill to find all connected empty spaces
+            while let Some(point) = queue.pop_front() {
+                for (r, c) in neighbors(size, point) {
+                    match board[r][c] {
+                        Stone::Empty => {
+                            if visited.insert((r, c)) {
+                                region.push((r, c));
+                                queue.push_back((r, c));
+                            }
+                        }
+                        Stone::Black => has_black = true,
+                        Stone::White => has_white = true,
+                    }
+                }
+            }
+
+            // Territory belongs to a player if only their stones border it
+            let owner = match (has_black, has_white) {
+                (true, false) => Stone::Black,
+                (false, true) => Stone::White,
+                _ => continue,
+            };
+            for (r, c) in region {
+                owners[r][c] = owner;
+            }
         }
-        neighbors
     }
+
+    owners
 }
 
 // Struct: Game state - board, players, captures, game status
 #[derive(Debug, Clone)]
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct GameState {
-    board: [[Stone; BOARD_SIZE]; BOARD_SIZE],
+    size: usize,
+    board: Vec<Vec<Stone>>,
+    chains: Chains, // Same stones as board, with chains and liberties kept up to date
     current_player: Stone,
-    previous_board: Option<[[Stone; BOARD_SIZE]; BOARD_SIZE]>, // For ko rule
+    previous_hash: Option<u64>, // Position before the opponent's last move, for ko
     black_captured: usize,
     white_captured: usize,
     consecutive_passes: usize,
     game_over: bool,
     last_move: Option<(usize, usize)>,
+    ruleset: Ruleset,
+    komi: f64,
+    ko_rule: KoRule,
+    position_history: PositionHistory, // Zobrist hash + side to move, for superko
+    moves: Vec<MoveRecord>,
+    move_number: usize, // Moves played; records beyond this can be redone
+    handicap: usize,
+    handicap_placement: HandicapPlacement,
+    handicap_to_place: usize, // Free handicap stones Black has yet to place
+    black_name: Option<String>,
+    white_name: Option<String>,
+    result: Option<GameResult>, // SGF RE value
+    comment: Option<String>, // Game (root node) comment
+    phase: GamePhase,
+    dead_stones: HashSet<(usize, usize)>, // Marked dead during the scoring phase
+    black_accepted: bool,
+    white_accepted: bool,
+    final_score: Option<Score>, // Agreed scores once both players accept
+    clock: Option<GameClock>,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GameState {
-    // Constructor: Create new game with empty board, Black to play
-    #[wasm_bindgen(constructor)]
+    // Constructor: Create new 19x19 game with empty board, Black to play
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> GameState {
+        GameState::with_size(DEFAULT_BOARD_SIZE)
+    }
+
+    // Constructor: Create new game on an NxN board (size clamped to 2..=25), Japanese rules
+    pub fn with_size(size: usize) -> GameState {
+        GameState::with_rules(size, Ruleset::Japanese)
+    }
+
+    // Constructor: Create new game on an NxN board under the given ruleset (komi from ruleset)
+    pub fn with_rules(size: usize, ruleset: Ruleset) -> GameState {
+        let size = size.clamp(MIN_BOARD_SIZE, MAX_BOARD_SIZE);
         GameState {
-            board: [[Stone::Empty; BOARD_SIZE]; BOARD_SIZE],
+            size,
+            board: vec![vec![Stone::Empty; size]; size],
+            chains: Chains::new(size),
             current_player: Stone::Black,
-            previous_board: None,
+            previous_hash: None,
             black_captured: 0,
             white_captured: 0,
             consecutive_passes: 0,
             game_over: false,
             last_move: None,
+            ruleset,
+            komi: ruleset.default_komi(),
+            ko_rule: ruleset.ko_rule(),
+            position_history: PositionHistory::new(0, Stone::Black), // Empty board, Black to move
+            moves: Vec::new(),
+            move_number: 0,
+            handicap: 0,
+            handicap_placement: HandicapPlacement::Fixed,
+            handicap_to_place: 0,
+            black_name: None,
+            white_name: None,
+            result: None,
+            comment: None,
+            phase: GamePhase::Playing,
+            dead_stones: HashSet::new(),
+            black_accepted: false,
+            white_accepted: false,
+            final_score: None,
+            clock: None,
         }
     }
 
     // Getters: Expose game state properties to JavaScript
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn current_player(&self) -> Stone {
         self.current_player
     }
 
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn game_over(&self) -> bool {
         self.game_over
     }
 
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn black_captured(&self) -> usize {
         self.black_captured
     }
 
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
     pub fn white_captured(&self) -> usize {
         self.white_captured
     }
 
-    // Board: Get entire board as JavaScript array
-    pub fn get_board(&self) -> JsValue {
-        let mut board_array = Vec::new();
-        for row in 0..BOARD_SIZE {
-            for col in 0..BOARD_SIZE {
-                let value = match self.board[row][col] {
-                    Stone::Empty => 0,
-                    Stone::Black => 1,
-                    Stone::White => 2,
-                };
-                board_array.push(value);
-            }
-        }
-        serde_wasm_bindgen::to_value(&board_array).unwrap()
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn komi(&self) -> f64 {
+        self.komi
+    }
+
+    // Komi: Override the ruleset's default komi
+    #[cfg_attr(feature = "wasm", wasm_bindgen(setter))]
+    pub fn set_komi(&mut self, komi: f64) {
+        self.komi = komi;
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn handicap(&self) -> usize {
+        self.handicap
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn black_name(&self) -> Option<String> {
+        self.black_name.clone()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(setter))]
+    pub fn set_black_name(&mut self, name: Option<String>) {
+        self.black_name = name;
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn white_name(&self) -> Option<String> {
+        self.white_name.clone()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(setter))]
+    pub fn set_white_name(&mut self, name: Option<String>) {
+        self.white_name = name;
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn result(&self) -> Option<GameResult> {
+        self.result
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn ko_rule(&self) -> KoRule {
+        self.ko_rule
+    }
+
+    // Ko Rule: Override the ruleset's ko flavor (simple, positional or situational superko)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(setter))]
+    pub fn set_ko_rule(&mut self, ko_rule: KoRule) {
+        self.ko_rule = ko_rule;
     }
 
     // Stone: Get stone at specific position
     pub fn get_stone(&self, row: usize, col: usize) -> Stone {
-        if row >= BOARD_SIZE || col >= BOARD_SIZE {
+        if row >= self.size || col >= self.size {
             return Stone::Empty;
         }
         self.board[row][col]
     }
 
-    // Place: Place stone at position, handle captures, ko rule, and suicide
-    pub fn place_stone(&mut self, row: usize, col: usize) -> bool {
-        if self.game_over {
-            return false;
+    // Liberties: Liberties of the chain at (row, col) (0 for an empty point)
+    pub fn liberties(&self, row: usize, col: usize) -> usize {
+        if row >= self.size || col >= self.size {
+            return 0;
         }
+        self.chains.liberties(row, col)
+    }
 
-        if row >= BOARD_SIZE || col >= BOARD_SIZE {
-            return false;
-        }
+    // Place: Place stone at position for the player to move (false if illegal; try_play says why)
+    pub fn place_stone(&mut self, row: usize, col: usize) -> bool {
+        self.try_play(Move::Play((row, col))).is_ok()
+    }
 
-        if self.board[row][col] != Stone::Empty {
-            return false;
-        }
+    // Pass: Skip turn (two consecutive passes ends game; AGA requires White to pass last)
+    pub fn pass(&mut self) {
+        let _ = self.try_play(Move::Pass);
+    }
 
-        // Ko: Save board state before move
-        let board_before_move = self.board.clone();
+    // Play Stone: Place stone at position, handle captures, ko rule, and suicide (per ruleset)
+    fn play_stone(&mut self, row: usize, col: usize) -> Result<MoveOutcome, IllegalMove> {
+        let player = self.current_player;
 
-        // Place: Put stone on board
-        self.board[row][col] = self.current_player;
+        // Handicap: Free placement stones are setup, not moves
+        if self.handicap_to_place > 0 {
+            self.place_handicap_stone(row, col)?;
+            let point = Some((row, col));
+            return Ok(MoveOutcome { player, point, captured: Vec::new(), suicided: Vec::new() });
+        }
 
-        // Capture: Check neighbors for opponent groups to capture
-        let mut captured_count = 0;
-        let opponent = match self.current_player {
-            Stone::Black => Stone::White,
-            Stone::White => Stone::Black,
-            Stone::Empty => return false,
-        };
+        // Probe: Resolve captures and suicide without touching the board
+        let probe = self.probe_move(row, col)?;
 
-        let pos = Position::new(row, col);
-        for neighbor in pos.neighbors() {
-            if self.board[neighbor.row][neighbor.col] == opponent {
-                let captured = self.capture_group(neighbor.row, neighbor.col);
-                captured_count += captured;
-            }
+        if self.current_player == Stone::Empty {
+            return Err(IllegalMove::GameOver);
         }
+        let opponent = self.current_player.opponent();
 
-        // Suicide: Check if placed stone has liberties (not captured)
-        if self.count_liberties(row, col) == 0 {
-            if captured_count == 0 {
-                // Invalid: Suicide without capture
-                self.board = board_before_move;
-                return false;
-            }
-        }
+        // Ko: Reject positions forbidden by the active ko rule
+        self.check_ko(probe.hash, opponent)?;
 
-        // Update: Increment captured counts
-        match self.current_player {
-            Stone::Black => self.black_captured += captured_count,
-            Stone::White => self.white_captured += captured_count,
-            Stone::Empty => {}
+        // Play: Update chains, then mirror the changes on the board
+        let previous_hash = self.chains.hash();
+        let (captured, suicided) = self.chains.play(row, col, player);
+        self.board[row][col] = player;
+        for &(r, c) in captured.iter().chain(&suicided) {
+            self.board[r][c] = Stone::Empty;
         }
 
-        // Ko: Check if board state repeats (ko rule violation)
-        if let Some(ref prev_board) = self.previous_board {
-            if self.board == *prev_board {
-                // Invalid: Ko violation - revert move
-                self.board = board_before_move;
-                match self.current_player {
-                    Stone::Black => self.black_captured -= captured_count,
-                    Stone::White => self.white_captured -= captured_count,
-                    Stone::Empty => {}
-                }
-                return false;
+        // Update: Increment captured counts (suicided stones go to the opponent)
+        match self.current_player {
+            Stone::Black => {
+                self.black_captured += captured.len();
+                self.white_captured += suicided.len();
+            }
+            Stone::White => {
+                self.white_captured += captured.len();
+                self.black_captured += suicided.len();
             }
+            Stone::Empty => {}
         }
 
-        // Update: Save board state, record move, reset passes
-        self.previous_board = Some(board_before_move);
+        let outcome = MoveOutcome { player, point: Some((row, col)), captured, suicided };
+        self.record_move(MoveRecord {
+            player: self.current_player,
+            point: Some((row, col)),
+            captured: outcome.captured.clone(),
+            suicided: outcome.suicided.clone(),
+            black_captured: self.black_captured,
+            white_captured: self.white_captured,
+            comment: None,
+        });
+
+        // Update: Save previous position, record position, reset passes
+        self.previous_hash = Some(previous_hash);
+        self.position_history.push(self.chains.hash(), opponent);
         self.last_move = Some((row, col));
         self.consecutive_passes = 0;
 
-        // Switch: Change to opponent's turn
+        // Switch: Change to opponent's turn and clock
+        self.press_clock(self.current_player);
         self.current_player = opponent;
 
-        true
+        Ok(outcome)
     }
 
-    // Pass: Skip turn (two consecutive passes ends game)
-    pub fn pass(&mut self) {
-        if self.game_over {
-            return;
+    // Play Pass: Pass for the player to move
+    fn play_pass(&mut self) -> Result<MoveOutcome, IllegalMove> {
+        if self.handicap_to_place > 0 {
+            return Err(IllegalMove::HandicapPending);
         }
+        let player = self.current_player;
 
         self.consecutive_passes += 1;
         self.last_move = None;
 
-        // If both players pass consecutively, game ends
-        if self.consecutive_passes >= 2 {
+        // Ko: A pass resets simple ko (the opponent may retake on their next move)
+        self.previous_hash = Some(self.chains.hash());
+
+        // Pass Stones: Hand the opponent one prisoner (AGA)
+        if self.ruleset.pass_stones() {
+            match self.current_player {
+                Stone::Black => self.white_captured += 1,
+                Stone::White => self.black_captured += 1,
+                Stone::Empty => {}
+            }
+        }
+
+        self.record_move(MoveRecord {
+            player: self.current_player,
+            point: None,
+            captured: Vec::new(),
+            suicided: Vec::new(),
+            black_captured: self.black_captured,
+            white_captured: self.white_captured,
+            comment: None,
+        });
+
+        let next_player = self.current_player.opponent();
+
+        // Superko: Same stones with the other side to move is a new situation
+        self.position_history.push(self.chains.hash(), next_player);
+
+        // If both players pass consecutively, game ends and dead stones are agreed next
+        let ends = self.ruleset.ends_on_two_passes() || self.current_player == Stone::White;
+        if self.consecutive_passes >= 2 && ends {
             self.game_over = true;
+            self.start_scoring();
         } else {
             // Switch player
-            self.current_player = match self.current_player {
-                Stone::Black => Stone::White,
-                Stone::White => Stone::Black,
-                Stone::Empty => Stone::Black,
-            };
+            self.current_player = next_player;
         }
-    }
+        self.press_clock(player);
 
-    // Last Move: Get last played position as JavaScript array
-    pub fn get_last_move(&self) -> Option<JsValue> {
-        if let Some((row, col)) = self.last_move {
-            let result = vec![row as u32, col as u32];
-            serde_wasm_bindgen::to_value(&result).ok()
-        } else {
-            None
-        }
+        Ok(MoveOutcome { player, point: None, captured: Vec::new(), suicided: Vec::new() })
     }
 
-    // Reset: Start new game
+    // Reset: Start new game with the same board size, ruleset, komi, ko rule, handicap and time control
     pub fn reset(&mut self) {
-        *self = GameState::new();
-    }
+        let komi = self.komi;
+        let clock = self.clock.take();
 
-    // Capture: Remove opponent group with no liberties, return count captured
-    fn capture_group(&mut self, row: usize, col: usize) -> usize {
-        let stone = self.board[row][col];
-        if stone == Stone::Empty {
-            return 0;
+        // Handicap: Without a fixed layout (e.g. loaded from SGF), Black places the stones, or none if too many
+        let (handicap, placement) = (self.handicap, self.handicap_placement);
+        if !self.new_handicap(handicap, placement) && !self.new_handicap(handicap, HandicapPlacement::Free) {
+            self.new_handicap(0, HandicapPlacement::Fixed);
         }
-
-        // BFS: Find all stones in the connected group
-        let mut group = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back(Position::new(row, col));
-        group.insert((row, col));
-
-        while let Some(pos) = queue.pop_front() {
-            for neighbor in pos.neighbors() {
-                if self.board[neighbor.row][neighbor.col] == stone
-                    && !group.contains(&(neighbor.row, neighbor.col))
-                {
-                    group.insert((neighbor.row, neighbor.col));
-                    queue.push_back(neighbor);
-                }
-            }
+        self.komi = komi;
+        if let Some(mut clock) = clock {
+            clock.reset();
+            self.set_clock(clock);
         }
+    }
 
-        // Liberties: Check if group has any empty adjacent spaces
-        let mut has_liberty = false;
-        for &(r, c) in &group {
-            if self.count_liberties(r, c) > 0 {
-                has_liberty = true;
-                break;
-            }
+    // Probe: What the current player's stone at (row, col) would capture, and the resulting position's hash
+    // Fails if the stone cannot go there (ko not checked)
+    fn probe_move(&self, row: usize, col: usize) -> Result<Probe, IllegalMove> {
+        if row >= self.size || col >= self.size {
+            return Err(IllegalMove::OutOfBounds);
         }
 
-        // Capture: Remove group if no liberties
-        if !has_liberty {
-            for &(r, c) in &group {
-                self.board[r][c] = Stone::Empty;
-            }
-            return group.len();
+        if self.board[row][col] != Stone::Empty {
+            return Err(IllegalMove::Occupied);
         }
 
-        0
-    }
-
-    // Liberties: Count empty adjacent spaces for a stone/group
-    fn count_liberties(&self, row: usize, col: usize) -> usize {
-        let stone = self.board[row][col];
-        if stone == Stone::Empty {
-            return 0;
+        if self.current_player == Stone::Empty {
+            return Err(IllegalMove::GameOver);
         }
 
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back(Position::new(row, col));
-        visited.insert((row, col));
-
-        let mut liberties = 0;
-
-        while let Some(pos) = queue.pop_front() {
-            for neighbor in pos.neighbors() {
-                if visited.contains(&(neighbor.row, neighbor.col)) {
-                    continue;
-                }
-
-                match self.board[neighbor.row][neighbor.col] {
-                    Stone::Empty => {
-                        liberties += 1;
-                        visited.insert((neighbor.row, neighbor.col));
-                    }
-                    s if s == stone => {
-                        visited.insert((neighbor.row, neighbor.col));
-                        queue.push_back(neighbor);
-                    }
-                    _ => {
-                        visited.insert((neighbor.row, neighbor.col));
-                    }
-                }
-            }
+        // Suicide: Placed stone's chain has no liberties and nothing is captured
+        let probe = self.chains.probe(row, col, self.current_player);
+        if probe.suicide && !self.ruleset.allows_suicide(probe.group) {
+            return Err(IllegalMove::Suicide);
         }
 
-        liberties
+        Ok(probe)
     }
 
-    // Valid Move: Check if move is legal (not suicide, not ko, position empty)
+    // Ko: Check if a resulting position (with next_player to move) is forbidden by the active ko rule
+    fn check_ko(&self, hash: u64, next_player: Stone) -> Result<(), IllegalMove> {
+        self.ko_rule.check(self.previous_hash, &self.position_history, hash, next_player)
+    }
+
+    // Valid Move: Check if move is legal (position empty, not suicide, not ko/superko)
     pub fn is_valid_move(&self, row: usize, col: usize) -> bool {
-        if self.game_over {
-            return false;
-        }
+        self.check_move(row, col).is_ok()
+    }
 
-        if row >= BOARD_SIZE || col >= BOARD_SIZE {
+    // Star Point: Check if position is a hoshi (star point)
+    // Corners sit on the 4-4 points (3-3 below 13x13), tengen on odd boards,
+    // side stars on odd boards of 15x15 and up (9 points on 19x19, 5 on 9x9/13x13)
+    pub fn is_star_point(&self, row: usize, col: usize) -> bool {
+        if self.size < 7 || row >= self.size || col >= self.size {
             return false;
         }
-
-        if self.board[row][col] != Stone::Empty {
-            return false;
+        let edge = if self.size >= 13 { 3 } else { 2 };
+        let far = self.size - 1 - edge;
+        let center = self.size / 2;
+        let odd = self.size % 2 == 1;
+        let is_line = |i: usize| {
+            i == edge || i == far || (odd && i == center && self.size >= 15)
+        };
+        if odd && row == center && col == center {
+            return true;
         }
+        is_line(row) && is_line(col)
+    }
 
-        // Test: Simulate move on temporary board
-        let mut test_board = self.board.clone();
-        test_board[row][col] = self.current_player;
+    // Label: Get column label (A-S on 19x19, up to A-Y on 25x25)
+    pub fn get_column_label(&self, col: usize) -> String {
+        if col < self.size {
+            char::from(65 + col as u8).to_string()
+        } else {
+            String::new()
+        }
+    }
 
-        let opponent = match self.current_player {
-            Stone::Black => Stone::White,
-            Stone::White => Stone::Black,
-            Stone::Empty => return false,
-        };
+    // Label: Get row label (N-1, e.g. 19-1)
+    pub fn get_row_label(&self, row: usize) -> String {
+        if row < self.size {
+            (self.size - row).to_string()
+        } else {
+            String::new()
+        }
+    }
+}
 
-        // Capture: Check if move would capture opponent stones
-        let pos = Position::new(row, col);
-        let mut would_capture = false;
-        for neighbor in pos.neighbors() {
-            if test_board[neighbor.row][neighbor.col] == opponent {
-                // Check if neighbor group would be captured
-                let mut group = HashSet::new();
-                let mut queue = VecDeque::new();
-                queue.push_back(neighbor);
-                group.insert((neighbor.row, neighbor.col));
-
-                while let Some(p) = queue.pop_front() {
-                    for n in p.neighbors() {
-                        if test_board[n.row][n.col] == opponent
-                            && !group.contains(&(n.row, n.col))
-                        {
-                            group.insert((n.row, n.col));
-                            queue.push_back(n);
-                        }
-                    }
-                }
+impl GameState {
+    // Player: Set the side to move (for engines and editors that play either color)
+    pub fn set_current_player(&mut self, player: Stone) {
+        if player != Stone::Empty {
+            self.current_player = player;
+        }
+    }
 
-        // Liberties: Check if opponent group would have liberties after move
-        let mut has_liberty = false;
-        for &(r, c) in &group {
-            let mut lib_visited = HashSet::new();
-            let mut lib_queue = VecDeque::new();
-            lib_queue.push_back(Position::new(r, c));
-            lib_visited.insert((r, c));
-
-            while let Some(p) = lib_queue.pop_front() {
-                for n in p.neighbors() {
-                    if lib_visited.contains(&(n.row, n.col)) {
-                        continue;
-                    }
-                    match test_board[n.row][n.col] {
-                        Stone::Empty => {
-                            has_liberty = true;
-                            break;
-                        }
-                        s if s == opponent => {
-                            lib_visited.insert((n.row, n.col));
-                            lib_queue.push_back(n);
-                        }
-                        _ => {
-                            lib_visited.insert((n.row, n.col));
-                        }
-                    }
-                }
-                if has_liberty {
-                    break;
-                }
-            }
-            if has_liberty {
-                break;
-            }
+    // Play: Play a stone or pass for the player to move, reporting what it captured or why it is illegal
+    pub fn try_play(&mut self, mv: Move) -> Result<MoveOutcome, IllegalMove> {
+        if self.game_over || self.check_time() {
+            return Err(IllegalMove::GameOver);
+        }
+        match mv {
+            Move::Play((row, col)) => self.play_stone(row, col),
+            Move::Pass => self.play_pass(),
         }
+    }
 
-        if !has_liberty {
-            would_capture = true;
-            break;
+    // Check: Why a stone at (row, col) would be illegal for the player to move (Ok if legal)
+    pub fn check_move(&self, row: usize, col: usize) -> Result<(), IllegalMove> {
+        if self.game_over {
+            return Err(IllegalMove::GameOver);
         }
-            }
+
+        // Handicap: Any empty point takes a free placement stone
+        if self.handicap_to_place > 0 {
+            return match self.board.get(row).and_then(|line| line.get(col)) {
+                None => Err(IllegalMove::OutOfBounds),
+                Some(Stone::Empty) => Ok(()),
+                Some(_) => Err(IllegalMove::Occupied),
+            };
         }
 
-        // Liberties: Check if placed stone would have liberties
-        let mut has_liberty = false;
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back(pos);
-        visited.insert((row, col));
-
-        while let Some(p) = queue.pop_front() {
-            for neighbor in p.neighbors() {
-                if visited.contains(&(neighbor.row, neighbor.col)) {
-                    continue;
-                }
-                match test_board[neighbor.row][neighbor.col] {
-                    Stone::Empty => {
-                        has_liberty = true;
-                        break;
-                    }
-                    s if s == self.current_player => {
-                        visited.insert((neighbor.row, neighbor.col));
-                        queue.push_back(neighbor);
-                    }
-                    _ => {
-                        visited.insert((neighbor.row, neighbor.col));
-                    }
-                }
-            }
-            if has_liberty {
-                break;
-            }
+        if self.current_player == Stone::Empty {
+            return Err(IllegalMove::GameOver);
         }
+        let next_player = self.current_player.opponent();
 
-        // Valid: Move is legal if it captures or has liberties
-        would_capture || has_liberty
+        // Test: Probe move and check the resulting position against the ko rule
+        let probe = self.probe_move(row, col)?;
+        self.check_ko(probe.hash, next_player)
     }
 
-    // Star Point: Check if position is a hoshi (star point)
-    pub fn is_star_point(&self, row: usize, col: usize) -> bool {
-        (row == 3 || row == 9 || row == 15) && (col == 3 || col == 9 || col == 15)
+    // Board: Stones by [row][col]
+    pub fn board(&self) -> &[Vec<Stone>] {
+        &self.board
     }
 
-    // Valid Moves: Get all legal moves as JavaScript array
-    pub fn get_valid_moves(&self) -> JsValue {
-        let mut valid_moves = Vec::new();
-        if self.game_over {
-            return serde_wasm_bindgen::to_value(&valid_moves).unwrap();
-        }
-        
-        for row in 0..BOARD_SIZE {
-            for col in 0..BOARD_SIZE {
-                if self.is_valid_move(row, col) {
-                    valid_moves.push(vec![row as u32, col as u32]);
-                }
-            }
-        }
-        serde_wasm_bindgen::to_value(&valid_moves).unwrap()
+    // Last Move: Point of the last stone played (None after a pass)
+    pub fn last_move(&self) -> Option<Point> {
+        self.last_move
     }
 
-    // Label: Get column label (A-S)
-    pub fn get_column_label(&self, col: usize) -> String {
-        if col < 19 {
-            char::from(65 + col as u8).to_string()
-        } else {
-            String::new()
+    // Valid Moves: All legal moves for the player to move
+    pub fn valid_moves(&self) -> Vec<Point> {
+        if self.game_over {
+            return Vec::new();
         }
+        (0..self.size)
+            .flat_map(|row| (0..self.size).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.is_valid_move(row, col))
+            .collect()
     }
 
-    // Label: Get row label (19-1)
-    pub fn get_row_label(&self, row: usize) -> String {
-        (19 - row).to_string()
-    }
-
-    // Territory: Calculate territory for a player (empty spaces surrounded by that player's stones)
-    fn calculate_territory(&self, player: Stone) -> usize {
-        let mut visited = HashSet::new();
-        let mut territory = 0;
-        
-        for row in 0..BOARD_SIZE {
-            for col in 0..BOARD_SIZE {
-                if self.board[row][col] == Stone::Empty && !visited.contains(&(row, col)) {
-                    let mut group = HashSet::new();
-                    let mut queue = VecDeque::new();
-                    queue.push_back(Position::new(row, col));
-                    visited.insert((row, col));
-                    group.insert((row, col));
-                    
-                    let mut has_black = false;
-                    let mut has_white = false;
-                    
-                    // Flood fill to find all connected empty spaces
-                    while let Some(p) = queue.pop_front() {
-                        for neighbor in p.neighbors() {
-                            match self.board[neighbor.row][neighbor.col] {
-                                Stone::Empty => {
-                                    if !visited.contains(&(neighbor.row, neighbor.col)) {
-                                        visited.insert((neighbor.row, neighbor.col));
-                                        group.insert((neighbor.row, neighbor.col));
-                                        queue.push_back(neighbor);
-                                    }
-                                }
-                                Stone::Black => {
-                                    has_black = true;
-                                }
-                                Stone::White => {
-                                    has_white = true;
-                                }
-                            }
-                        }
-                    }
-                    
-                    // Territory belongs to player if only their stones border it
-                    if player == Stone::Black && has_black && !has_white {
-                        territory += group.len();
-                    } else if player == Stone::White && has_white && !has_black {
-                        territory += group.len();
-                    }
-                }
+    // Board View: Every intersection with stone, star point, valid move, last move and scoring info
+    pub fn board_view(&self) -> BoardView {
+        let (territory, seki) = match self.phase {
+            GamePhase::Playing => (None, HashSet::new()),
+            _ => {
+                let (territory, seki) = self.territory_and_seki();
+                (Some(territory), seki.into_iter().flatten().collect())
             }
-        }
-        
-        territory
-    }
-    
-    // Score: Calculate final scores with komi (returns [black_score, white_score] as JsValue)
-    pub fn calculate_scores(&self) -> JsValue {
-        const KOMI: f64 = 6.5; // Standard komi for White
-        
-        if !self.game_over {
-            return serde_wasm_bindgen::to_value(&vec![0.0, 0.0]).unwrap();
-        }
-        
-        // Calculate territory
-        let black_territory = self.calculate_territory(Stone::Black);
-        let white_territory = self.calculate_territory(Stone::White);
-        
-        // Final scores: territory + captured stones + komi (for White)
-        let black_score = black_territory as f64 + self.black_captured as f64;
-        let white_score = white_territory as f64 + self.white_captured as f64 + KOMI;
-        
-        serde_wasm_bindgen::to_value(&vec![black_score, white_score]).unwrap()
-    }
-    
-    // Board Data: Get all intersections with stone, star point, valid move, last move info
-    pub fn get_board_data(&self) -> JsValue {
-        #[derive(Serialize)]
-        struct IntersectionData {
-            row: usize,
-            col: usize,
-            stone: u8,
-            is_star_point: bool,
-            is_valid_move: bool,
-            is_last_move: bool,
-        }
-        
-        let mut board_data = Vec::new();
-        for row in 0..BOARD_SIZE {
-            for col in 0..BOARD_SIZE {
-                let stone_value = match self.board[row][col] {
-                    Stone::Empty => 0,
-                    Stone::Black => 1,
-                    Stone::White => 2,
-                };
-                let is_star = self.is_star_point(row, col);
-                let is_valid = !self.game_over && self.is_valid_move(row, col);
-                let is_last_move = if let Some((r, c)) = self.last_move {
-                    r == row && c == col && stone_value != 0
-                } else {
-                    false
-                };
-                
-                board_data.push(IntersectionData {
+        };
+
+        let mut intersections = Vec::new();
+        for row in 0..self.size {
+            for col in 0..self.size {
+                let stone = self.board[row][col];
+                intersections.push(Intersection {
                     row,
                     col,
-                    stone: stone_value,
-                    is_star_point: is_star,
-                    is_valid_move: is_valid,
-                    is_last_move,
+                    stone: stone.code(),
+                    is_star_point: self.is_star_point(row, col),
+                    is_valid_move: !self.game_over && self.is_valid_move(row, col),
+                    is_last_move: self.last_move == Some((row, col)) && stone != Stone::Empty,
+                    is_dead: self.is_dead(row, col),
+                    is_seki: seki.contains(&(row, col)),
+                    territory: territory.as_ref().map_or(0, |t| t[row][col].code()),
                 });
             }
         }
-        serde_wasm_bindgen::to_value(&board_data).unwrap()
+        BoardView { size: self.size, intersections }
+    }
+
+    // Score: Scores for the current position, whether or not the game is over
+    // Stones marked dead are removed first and counted as prisoners
+    pub fn score(&self) -> Score {
+        let board = self.scoring_board();
+        let count = |grid: &[Vec<Stone>], player: Stone| grid.iter().flatten().filter(|&&s| s == player).count();
+
+        // Calculate territory (seki eyes excluded per ruleset)
+        let territory = self.territory();
+        let black_territory = count(&territory, Stone::Black);
+        let white_territory = count(&territory, Stone::White);
+
+        // Final scores: territory + (prisoners or stones on board, per ruleset) + komi (for White)
+        let (black_extra, white_extra) = match self.ruleset.scoring() {
+            ScoringMethod::Territory => (
+                self.black_captured + self.dead_count(Stone::White),
+                self.white_captured + self.dead_count(Stone::Black),
+            ),
+            ScoringMethod::Area => (count(&board, Stone::Black), count(&board, Stone::White)),
+        };
+        let black = (black_territory + black_extra) as f64;
+        let white = (white_territory + white_extra) as f64 + self.komi;
+        Score { black, white }
     }
 }
 
-// Init: Initialize WebAssembly module (set up panic hook for better error messages)
-#[wasm_bindgen]
-pub fn init() {
-    console_error_panic_hook::set_once();
-}
\ No newline at end of file
+impl Default for GameState {
+    fn default() -> Self {
+        GameState::new()
+    }
+}
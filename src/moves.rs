@@ -0,0 +1,49 @@
+// Imports: Serialization and game types
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{Point, Stone};
+
+// Enum: A move for the player to move - a stone on a point, or a pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Move {
+    Play(Point),
+    Pass,
+}
+
+// Struct: What a legal move did
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MoveOutcome {
+    pub player: Stone,
+    pub point: Option<Point>,   // None for a pass
+    pub captured: Vec<Point>,   // Opponent stones removed
+    pub suicided: Vec<Point>,   // Own stones removed (suicide rulesets)
+}
+
+// Enum: Why a move was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IllegalMove {
+    Occupied,
+    OutOfBounds,
+    Suicide,
+    Ko,      // Retakes a ko immediately (recreates the position before the opponent's move)
+    Superko, // Recreates an earlier whole-board position
+    GameOver,
+    HandicapPending, // Black has free handicap stones left to place; passing is not allowed
+}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IllegalMove::Occupied => write!(f, "point is occupied"),
+            IllegalMove::OutOfBounds => write!(f, "point is off the board"),
+            IllegalMove::Suicide => write!(f, "suicide is not allowed"),
+            IllegalMove::Ko => write!(f, "ko cannot be retaken immediately"),
+            IllegalMove::Superko => write!(f, "move repeats an earlier position (superko)"),
+            IllegalMove::GameOver => write!(f, "game is over"),
+            IllegalMove::HandicapPending => write!(f, "handicap stones must be placed first"),
+        }
+    }
+}
+
+impl std::error::Error for IllegalMove {}
@@ -0,0 +1,287 @@
+// Imports: WebAssembly bindings and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::bot::handicap_move;
+use crate::ladder::ladder_works;
+use crate::{neighbors, Board, GameState, IllegalMove, Mcts, Move, MoveOutcome, Rng, Stone};
+
+// Trait: Anything that can choose a move for the player to move
+pub trait Player {
+    fn select_move(&mut self, state: &GameState) -> Move;
+}
+
+// Enum: Named bot strengths, weakest first - exported to JavaScript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum Difficulty {
+    #[default]
+    Kyu30,
+    Kyu25,
+    Kyu20,
+    Kyu15,
+    Kyu10,
+}
+
+// Struct: Uniform random legal move that does not fill its own eyes (30k)
+#[derive(Debug, Clone)]
+pub struct RandomPlayer {
+    rng: Rng,
+}
+
+// Struct: Takes the biggest capture, else saves a chain in atari, else plays at random (25k)
+#[derive(Debug, Clone)]
+pub struct GreedyPlayer {
+    rng: Rng,
+}
+
+// Struct: Random move weighted by its local shape - captures, atari escapes, contact and
+// replies near the last move are favoured, self-atari and the first line avoided (20k)
+#[derive(Debug, Clone)]
+pub struct PatternPlayer {
+    rng: Rng,
+}
+
+// Struct: Player at a difficulty level - exported to JavaScript
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Bot {
+    difficulty: Difficulty,
+    player: Box<dyn Player>,
+}
+
+// Struct: Local features of a candidate move for the player to move
+#[derive(Debug, Clone, Copy, Default)]
+struct Features {
+    captures: usize,  // Opponent stones taken
+    escapes: bool,    // Gives an own chain in atari more than one liberty, not into a working ladder
+    ataris: bool,     // Leaves an adjacent opponent chain with one liberty
+    self_atari: bool, // Captures nothing and leaves the new chain with one liberty
+    contact: bool,    // Touches an opponent stone
+    near_last: bool,  // Within the 3x3 square around the last move
+    edge: bool,       // On the first line
+}
+
+impl Difficulty {
+    // Name: Kyu rank, e.g. "30k"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Kyu30 => "30k",
+            Difficulty::Kyu25 => "25k",
+            Difficulty::Kyu20 => "20k",
+            Difficulty::Kyu15 => "15k",
+            Difficulty::Kyu10 => "10k",
+        }
+    }
+
+    // Parse: Difficulty from its name ("30k" .. "10k")
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        Difficulty::all().into_iter().find(|d| d.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    // All: Every level, weakest first
+    pub fn all() -> [Difficulty; 5] {
+        [Difficulty::Kyu30, Difficulty::Kyu25, Difficulty::Kyu20, Difficulty::Kyu15, Difficulty::Kyu10]
+    }
+
+    // Player: Player of this strength (MCTS levels use a fixed playout count, so play does not depend on speed)
+    pub fn player(&self, seed: u64) -> Box<dyn Player> {
+        let rng = Rng::new(seed);
+        match self {
+            Difficulty::Kyu30 => Box::new(RandomPlayer { rng }),
+            Difficulty::Kyu25 => Box::new(GreedyPlayer { rng }),
+            Difficulty::Kyu20 => Box::new(PatternPlayer { rng }),
+            Difficulty::Kyu15 => Box::new(Mcts::with_playouts(300, seed as u32)),
+            Difficulty::Kyu10 => Box::new(Mcts::with_playouts(3000, seed as u32)),
+        }
+    }
+}
+
+impl RandomPlayer {
+    pub fn new(seed: u64) -> RandomPlayer {
+        RandomPlayer { rng: Rng::new(seed) }
+    }
+}
+
+impl GreedyPlayer {
+    pub fn new(seed: u64) -> GreedyPlayer {
+        GreedyPlayer { rng: Rng::new(seed) }
+    }
+}
+
+impl PatternPlayer {
+    pub fn new(seed: u64) -> PatternPlayer {
+        PatternPlayer { rng: Rng::new(seed) }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Bot {
+    // Create: Bot at a difficulty level; the same seed replays the same game
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(difficulty: Difficulty, seed: u32) -> Bot {
+        Bot { difficulty, player: difficulty.player(seed as u64) }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+}
+
+impl Player for RandomPlayer {
+    fn select_move(&mut self, state: &GameState) -> Move {
+        if let Some(mv) = forced_move(state) {
+            return mv;
+        }
+        state.random_move(&mut self.rng)
+    }
+}
+
+impl Player for GreedyPlayer {
+    fn select_move(&mut self, state: &GameState) -> Move {
+        if let Some(mv) = forced_move(state) {
+            return mv;
+        }
+        let player = state.current_player();
+        let mut best = None;
+        let mut best_value = 0;
+        for (row, col) in state.valid_moves() {
+            if state.is_own_eye(row, col, player) {
+                continue;
+            }
+            // Value: Stones captured first, then a rescue (worth less than any capture)
+            let features = features(state, row, col);
+            let value = features.captures * 2 + usize::from(features.escapes);
+            if value > best_value {
+                best = Some((row, col));
+                best_value = value;
+            }
+        }
+        best.map_or_else(|| state.random_move(&mut self.rng), Move::Play)
+    }
+}
+
+impl Player for PatternPlayer {
+    fn select_move(&mut self, state: &GameState) -> Move {
+        if let Some(mv) = forced_move(state) {
+            return mv;
+        }
+        let player = state.current_player();
+        let moves: Vec<(usize, usize)> = state
+            .valid_moves()
+            .into_iter()
+            .filter(|&(row, col)| !state.is_own_eye(row, col, player))
+            .collect();
+        if moves.is_empty() {
+            return Move::Pass;
+        }
+        let weights: Vec<f64> = moves.iter().map(|&(row, col)| weight(features(state, row, col))).collect();
+        let total: f64 = weights.iter().sum();
+
+        // Sample: Pick a move with probability proportional to its weight
+        let mut target = (self.rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64 * total;
+        for (&point, &weight) in moves.iter().zip(&weights) {
+            if target < weight {
+                return Move::Play(point);
+            }
+            target -= weight;
+        }
+        Move::Play(moves[moves.len() - 1])
+    }
+}
+
+impl Player for Mcts {
+    fn select_move(&mut self, state: &GameState) -> Move {
+        self.search(state)
+    }
+}
+
+impl Player for Bot {
+    fn select_move(&mut self, state: &GameState) -> Move {
+        self.player.select_move(state)
+    }
+}
+
+impl GameState {
+    // Generate: Let a player choose and play a move for the player to move
+    pub fn genmove<P: Player + ?Sized>(&mut self, player: &mut P) -> Result<MoveOutcome, IllegalMove> {
+        let mv = player.select_move(self);
+        self.try_play(mv)
+    }
+}
+
+// Forced: Pass once the game is over, and place handicap stones before anything else
+fn forced_move(state: &GameState) -> Option<Move> {
+    if state.game_over() {
+        return Some(Move::Pass);
+    }
+    (state.handicap_to_place() > 0).then(|| handicap_move(state))
+}
+
+// Features: Local shape of a stone for the player to move at the empty point (row, col)
+fn features(state: &GameState, row: usize, col: usize) -> Features {
+    let size = state.size();
+    let player = state.current_player();
+    let edge = row == 0 || col == 0 || row + 1 == size || col + 1 == size;
+    let mut features = Features { edge, ..Features::default() };
+    let after = state.chains.liberties_after(row, col, player);
+
+    // Neighbors: Each adjacent chain is looked at once (chains are named by their first stone)
+    let mut seen = Vec::new();
+    for (r, c) in neighbors(size, (row, col)) {
+        let stone = state.get_stone(r, c);
+        if stone == Stone::Empty {
+            continue;
+        }
+        let chain = state.chains.chain(r, c);
+        if seen.contains(&chain[0]) {
+            continue;
+        }
+        seen.push(chain[0]);
+        let liberties = state.liberties(r, c);
+        if stone == player {
+            features.escapes |= liberties == 1 && after > 1;
+        } else {
+            features.contact = true;
+            match liberties {
+                1 => features.captures += chain.len(),
+                2 => features.ataris = true,
+                _ => {}
+            }
+        }
+    }
+    // Ladder: Running into a working ladder only loses more stones
+    if features.escapes && features.captures == 0 && after == 2 {
+        let mut next = state.chains.clone();
+        next.play(row, col, player);
+        features.escapes = !ladder_works(&next, (row, col), player);
+    }
+    features.self_atari = features.captures == 0 && after <= 1;
+    features.ataris &= !features.self_atari;
+    features.near_last = state.last_move().is_some_and(|(r, c)| r.abs_diff(row) <= 1 && c.abs_diff(col) <= 1);
+    features
+}
+
+// Weight: Relative chance of the pattern player choosing a move with these features
+fn weight(features: Features) -> f64 {
+    let mut weight = 1.0 + 40.0 * features.captures as f64;
+    if features.escapes {
+        weight += 20.0;
+    }
+    if features.ataris {
+        weight += 4.0;
+    }
+    if features.contact {
+        weight += 2.0;
+    }
+    if features.near_last {
+        weight *= 3.0;
+    }
+    if features.edge {
+        weight *= 0.3;
+    }
+    if features.self_atari {
+        weight *= 0.05;
+    }
+    weight
+}
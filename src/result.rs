@@ -0,0 +1,163 @@
+// Imports: WebAssembly bindings, serialization, and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{GamePhase, GameState, Stone};
+
+// Enum: How a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum ResultReason {
+    Score,
+    Resign,
+    Time,
+    Forfeit,
+    Draw,
+    Void, // No result (e.g. triple ko, or declared void by the referee)
+}
+
+// Struct: Game result - winner (Empty for draw/void), margin for counted games, and reason
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct GameResult {
+    pub winner: Stone,
+    pub margin: Option<f64>, // Points, for games decided by counting (None if unknown)
+    pub reason: ResultReason,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameResult {
+    // Text: Standard result string (B+R, W+3.5, B+T, B+F, 0, Void)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = toString))]
+    pub fn to_string_js(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl GameResult {
+    // Create: Win for a player by the given reason
+    pub fn win(winner: Stone, reason: ResultReason) -> GameResult {
+        GameResult { winner, margin: None, reason }
+    }
+
+    // Create: Result of counting (draw on equal scores)
+    pub fn from_scores(black: f64, white: f64) -> GameResult {
+        let (winner, reason) = match black - white {
+            margin if margin > 0.0 => (Stone::Black, ResultReason::Score),
+            margin if margin < 0.0 => (Stone::White, ResultReason::Score),
+            _ => (Stone::Empty, ResultReason::Draw),
+        };
+        let margin = Some((black - white).abs()).filter(|_| reason == ResultReason::Score);
+        GameResult { winner, margin, reason }
+    }
+
+    // Parse: SGF RE value ("B+R", "W+Resign", "B+3.5", "W+T", "B+", "0", "Draw", "Void")
+    // Returns None for unknown ("?") or free-form results
+    pub fn parse(text: &str) -> Option<GameResult> {
+        let text = text.trim();
+        match text {
+            "0" | "Draw" | "Jigo" => {
+                return Some(GameResult { winner: Stone::Empty, margin: None, reason: ResultReason::Draw })
+            }
+            "Void" => return Some(GameResult { winner: Stone::Empty, margin: None, reason: ResultReason::Void }),
+            _ => {}
+        }
+
+        let (color, detail) = text.split_once('+')?;
+        let winner = match color {
+            "B" => Stone::Black,
+            "W" => Stone::White,
+            _ => return None,
+        };
+        let result = match detail {
+            "R" | "Resign" => GameResult::win(winner, ResultReason::Resign),
+            "T" | "Time" => GameResult::win(winner, ResultReason::Time),
+            "F" | "Forfeit" => GameResult::win(winner, ResultReason::Forfeit),
+            "" => GameResult::win(winner, ResultReason::Score),
+            margin => GameResult { winner, margin: Some(margin.parse().ok()?), reason: ResultReason::Score },
+        };
+        Some(result)
+    }
+}
+
+// Display: Standard result string, as stored in SGF RE
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let color = match self.winner {
+            Stone::Black => "B",
+            Stone::White => "W",
+            Stone::Empty => "",
+        };
+        match self.reason {
+            ResultReason::Draw => write!(f, "0"),
+            ResultReason::Void => write!(f, "Void"),
+            ResultReason::Resign => write!(f, "{}+R", color),
+            ResultReason::Time => write!(f, "{}+T", color),
+            ResultReason::Forfeit => write!(f, "{}+F", color),
+            ResultReason::Score => match self.margin {
+                Some(margin) => write!(f, "{}+{}", color, margin),
+                None => write!(f, "{}+", color),
+            },
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    // Resign: The player gives up; the opponent wins by resignation
+    pub fn resign(&mut self, player: Stone) -> bool {
+        self.forfeit_as(player, ResultReason::Resign)
+    }
+
+    // Time: The player's clock ran out; the opponent wins on time
+    pub fn forfeit_on_time(&mut self, player: Stone) -> bool {
+        self.forfeit_as(player, ResultReason::Time)
+    }
+
+    // Forfeit: The player loses by forfeit (rules violation, absence)
+    pub fn forfeit(&mut self, player: Stone) -> bool {
+        self.forfeit_as(player, ResultReason::Forfeit)
+    }
+
+    // No Result: End the game void, with no winner
+    pub fn declare_no_result(&mut self) -> bool {
+        if self.phase == GamePhase::Finished {
+            return false;
+        }
+        self.finish_early(GameResult { winner: Stone::Empty, margin: None, reason: ResultReason::Void });
+        true
+    }
+}
+
+impl GameState {
+    // Forfeit: End the game with the player's opponent winning for the given reason
+    fn forfeit_as(&mut self, player: Stone, reason: ResultReason) -> bool {
+        if player == Stone::Empty {
+            return false;
+        }
+        let winner = player.opponent();
+        if self.phase == GamePhase::Finished {
+            return false;
+        }
+        self.finish_early(GameResult::win(winner, reason));
+        true
+    }
+
+    // Finish Early: End the game before the count (resign, time, forfeit, void), dropping any dead-stone marks
+    pub(crate) fn finish_early(&mut self, result: GameResult) {
+        self.clear_scoring();
+        self.finish(result);
+    }
+
+    // Finish: Stop play and the clock with a final result (dead-stone marks stay for the final count)
+    pub(crate) fn finish(&mut self, result: GameResult) {
+        if let Some(clock) = &mut self.clock {
+            clock.stop();
+        }
+        self.game_over = true;
+        self.phase = GamePhase::Finished;
+        self.result = Some(result);
+    }
+}
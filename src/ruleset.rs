@@ -0,0 +1,276 @@
+// Imports: WebAssembly bindings and serialization
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::BuildHasherDefault;
+
+use crate::zobrist::KeyHasher;
+use crate::{IllegalMove, Stone};
+
+// Enum: Supported rulesets - exported to JavaScript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum Ruleset {
+    #[default]
+    Japanese,
+    Chinese,
+    Aga,
+    NewZealand,
+    TrompTaylor,
+    Ing,
+}
+
+// Enum: Scoring method (area = stones + territory, territory = territory + prisoners)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum ScoringMethod {
+    Area,
+    Territory,
+}
+
+// Enum: Ko flavor (simple ko or superko over whole-board positions)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum KoRule {
+    Simple,
+    PositionalSuperko,
+    SituationalSuperko,
+}
+
+impl Ruleset {
+    // Scoring: Area or territory counting
+    pub fn scoring(&self) -> ScoringMethod {
+        match self {
+            Ruleset::Japanese | Ruleset::Aga => ScoringMethod::Territory,
+            Ruleset::Chinese | Ruleset::NewZealand | Ruleset::TrompTaylor | Ruleset::Ing => {
+                ScoringMethod::Area
+            }
+        }
+    }
+
+    // Komi: Default compensation for White on an even game
+    pub fn default_komi(&self) -> f64 {
+        match self {
+            Ruleset::Japanese => 6.5,
+            Ruleset::Chinese | Ruleset::Aga | Ruleset::TrompTaylor => 7.5,
+            Ruleset::NewZealand => 7.0,
+            Ruleset::Ing => 8.0,
+        }
+    }
+
+    // Suicide: Whether a move that leaves its own group (of given size) without liberties is legal
+    // Ing only permits suicide of more than one stone
+    pub fn allows_suicide(&self, group_size: usize) -> bool {
+        match self {
+            Ruleset::NewZealand | Ruleset::TrompTaylor => true,
+            Ruleset::Ing => group_size > 1,
+            Ruleset::Japanese | Ruleset::Chinese | Ruleset::Aga => false,
+        }
+    }
+
+    // Ko: Which repetition rule applies
+    pub fn ko_rule(&self) -> KoRule {
+        match self {
+            Ruleset::Japanese => KoRule::Simple,
+            Ruleset::Chinese | Ruleset::TrompTaylor => KoRule::PositionalSuperko,
+            Ruleset::Aga | Ruleset::NewZealand | Ruleset::Ing => KoRule::SituationalSuperko,
+        }
+    }
+
+    // Pass Stones: Whether passing hands the opponent one prisoner (AGA)
+    pub fn pass_stones(&self) -> bool {
+        matches!(self, Ruleset::Aga)
+    }
+
+    // End: Whether any two consecutive passes end the game
+    // AGA instead requires White to make the final pass
+    pub fn ends_on_two_passes(&self) -> bool {
+        !matches!(self, Ruleset::Aga)
+    }
+
+    // Seki: Whether eyes of groups in seki count as territory (not under Japanese rules)
+    pub fn counts_seki_eyes(&self) -> bool {
+        !matches!(self, Ruleset::Japanese)
+    }
+
+    // Name: Human-readable ruleset name (also used for SGF RU)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ruleset::Japanese => "Japanese",
+            Ruleset::Chinese => "Chinese",
+            Ruleset::Aga => "AGA",
+            Ruleset::NewZealand => "NZ",
+            Ruleset::TrompTaylor => "Tromp-Taylor",
+            Ruleset::Ing => "Ing",
+        }
+    }
+}
+
+// Struct: Every position reached so far (Zobrist hash + side to move), for superko
+// Kept in order so undo can drop the latest, and counted by hash so lookups take constant time
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PositionHistory {
+    positions: Vec<(u64, Stone)>,
+    counts: HashMap<u64, [u32; 2], BuildHasherDefault<KeyHasher>>, // Times reached with Black, White to move
+}
+
+impl PositionHistory {
+    // Create: History starting from one position
+    pub(crate) fn new(hash: u64, to_move: Stone) -> PositionHistory {
+        let mut history = PositionHistory::default();
+        history.push(hash, to_move);
+        history
+    }
+
+    pub(crate) fn push(&mut self, hash: u64, to_move: Stone) {
+        self.positions.push((hash, to_move));
+        self.counts.entry(hash).or_default()[side(to_move)] += 1;
+    }
+
+    // Pop: Forget the latest position (undo)
+    pub(crate) fn pop(&mut self) {
+        if let Some((hash, to_move)) = self.positions.pop() {
+            let counts = self.counts.get_mut(&hash).expect("every position is counted");
+            counts[side(to_move)] -= 1;
+            if counts == &[0, 0] {
+                self.counts.remove(&hash);
+            }
+        }
+    }
+
+    // Contains: Whether the board position has been reached, with either side to move
+    pub(crate) fn contains(&self, hash: u64) -> bool {
+        self.counts.contains_key(&hash)
+    }
+
+    // Contains: Whether the board position has been reached with to_move to move
+    pub(crate) fn contains_situation(&self, hash: u64, to_move: Stone) -> bool {
+        self.counts.get(&hash).is_some_and(|counts| counts[side(to_move)] > 0)
+    }
+}
+
+// Side: Count slot for the side to move
+fn side(to_move: Stone) -> usize {
+    match to_move {
+        Stone::White => 1,
+        _ => 0,
+    }
+}
+
+impl KoRule {
+    // Check: Whether a resulting position (by hash, with next_player to move) is a forbidden repetition
+    // previous is the position before the opponent's last move; history holds every earlier position
+    // Retaking a ko is reported as Ko under every rule; other repetitions as Superko
+    pub(crate) fn check(
+        &self,
+        previous: Option<u64>,
+        history: &PositionHistory,
+        hash: u64,
+        next_player: Stone,
+    ) -> Result<(), IllegalMove> {
+        let retakes_ko = previous == Some(hash);
+        let repeated = match self {
+            // Simple: May not recreate the position before the opponent's last move
+            KoRule::Simple => retakes_ko,
+            // Positional: May not recreate any earlier board position
+            KoRule::PositionalSuperko => history.contains(hash),
+            // Situational: May not recreate an earlier board position with the same side to move
+            KoRule::SituationalSuperko => history.contains_situation(hash, next_player),
+        };
+        match (repeated, retakes_ko) {
+            (false, _) => Ok(()),
+            (true, true) => Err(IllegalMove::Ko),
+            (true, false) => Err(IllegalMove::Superko),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameState, Move};
+
+    // Kos: Three kos side by side on row 2, starting at these columns
+    const KOS: [usize; 3] = [1, 7, 13];
+
+    // Triple Ko: Black holds the first ko, White the other two, Black to move
+    // Each ko: Black takes by playing (2, col + 2), White takes back at (2, col + 1)
+    fn triple_ko(ruleset: Ruleset) -> GameState {
+        let mut stones = Vec::new();
+        for (i, &col) in KOS.iter().enumerate() {
+            stones.extend([(1, col + 1, Stone::Black), (2, col, Stone::Black), (3, col + 1, Stone::Black)]);
+            stones.extend([(1, col + 2, Stone::White), (2, col + 3, Stone::White), (3, col + 2, Stone::White)]);
+            stones.push(if i == 0 { (2, col + 2, Stone::Black) } else { (2, col + 1, Stone::White) });
+        }
+        let mut game = GameState::with_rules(19, ruleset);
+        game.apply_setup(&stones, Stone::Black);
+        game
+    }
+
+    // Cycle: Each side takes a ko in turn until the starting position returns on the sixth move
+    fn cycle(game: &mut GameState) -> Result<(), IllegalMove> {
+        let [a, b, c] = KOS;
+        for point in [(2, b + 2), (2, a + 1), (2, c + 2), (2, b + 1), (2, a + 2)] {
+            game.try_play(Move::Play(point)).expect("no earlier position repeats");
+        }
+        game.try_play(Move::Play((2, c + 1))).map(|_| ())
+    }
+
+    #[test]
+    fn retaking_a_ko_at_once_is_ko_under_every_rule() {
+        for ruleset in [Ruleset::Japanese, Ruleset::Chinese, Ruleset::Aga, Ruleset::TrompTaylor] {
+            let mut game = triple_ko(ruleset);
+            game.try_play(Move::Play((2, KOS[1] + 2))).unwrap();
+            assert_eq!(game.try_play(Move::Play((2, KOS[1] + 1))), Err(IllegalMove::Ko), "{:?}", ruleset);
+        }
+    }
+
+    #[test]
+    fn triple_ko_cycles_under_simple_ko() {
+        let mut game = triple_ko(Ruleset::Japanese);
+        assert_eq!(cycle(&mut game), Ok(()));
+    }
+
+    #[test]
+    fn triple_ko_is_superko_under_positional_and_situational_rules() {
+        for ruleset in [Ruleset::Chinese, Ruleset::TrompTaylor, Ruleset::Aga, Ruleset::NewZealand, Ruleset::Ing] {
+            let mut game = triple_ko(ruleset);
+            assert_eq!(cycle(&mut game), Err(IllegalMove::Superko), "{:?}", ruleset);
+        }
+    }
+
+    #[test]
+    fn positional_superko_ignores_the_side_to_move() {
+        let mut history = PositionHistory::new(0, Stone::Black);
+        history.push(7, Stone::White);
+        assert_eq!(KoRule::PositionalSuperko.check(None, &history, 7, Stone::Black), Err(IllegalMove::Superko));
+        assert_eq!(KoRule::SituationalSuperko.check(None, &history, 7, Stone::Black), Ok(()));
+        assert_eq!(KoRule::SituationalSuperko.check(None, &history, 7, Stone::White), Err(IllegalMove::Superko));
+        assert_eq!(KoRule::Simple.check(None, &history, 7, Stone::White), Ok(()));
+    }
+
+    #[test]
+    fn undo_forgets_only_the_latest_position() {
+        let mut history = PositionHistory::new(0, Stone::Black);
+        history.push(7, Stone::White);
+        history.push(7, Stone::Black);
+        history.pop();
+        assert!(history.contains_situation(7, Stone::White));
+        assert!(!history.contains_situation(7, Stone::Black));
+        history.pop();
+        assert!(!history.contains(7));
+        assert!(history.contains(0));
+    }
+
+    #[test]
+    fn repeating_the_previous_position_is_ko() {
+        let mut history = PositionHistory::new(0, Stone::Black);
+        history.push(7, Stone::White);
+        history.push(9, Stone::Black);
+        for rule in [KoRule::Simple, KoRule::PositionalSuperko, KoRule::SituationalSuperko] {
+            assert_eq!(rule.check(Some(7), &history, 7, Stone::White), Err(IllegalMove::Ko), "{:?}", rule);
+            assert_eq!(rule.check(Some(7), &history, 11, Stone::White), Ok(()), "{:?}", rule);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+// Imports: WebAssembly bindings, serialization, and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::chains::Chains;
+use crate::{neighbors, territory_map, GameResult, GameState, Point, Stone};
+
+// Enum: Game phase - playing, agreeing on dead stones after two passes, or finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum GamePhase {
+    #[default]
+    Playing,
+    Scoring,
+    Finished,
+}
+
+// Struct: Black and White scores, komi included
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Score {
+    pub black: f64,
+    pub white: f64,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn phase(&self) -> GamePhase {
+        self.phase
+    }
+
+    // Dead Stones: Mark or unmark the whole chain at (row, col) as dead (scoring phase only)
+    // Pass-alive chains cannot be marked dead; any change withdraws both players' acceptance
+    pub fn toggle_dead_group(&mut self, row: usize, col: usize) -> bool {
+        if self.phase != GamePhase::Scoring || row >= self.size || col >= self.size {
+            return false;
+        }
+        if self.board[row][col] == Stone::Empty {
+            return false;
+        }
+        let group = self.chains.chain(row, col);
+        let marking = !self.dead_stones.contains(&(row, col));
+        if marking && self.pass_alive(self.board[row][col]).chains.contains(&group) {
+            return false;
+        }
+        if self.dead_stones.contains(&(row, col)) {
+            self.dead_stones.retain(|point| !group.contains(point));
+        } else {
+            self.dead_stones.extend(group);
+        }
+        self.black_accepted = false;
+        self.white_accepted = false;
+        true
+    }
+
+    // Dead Stones: Check if the stone at (row, col) is marked dead
+    pub fn is_dead(&self, row: usize, col: usize) -> bool {
+        self.dead_stones.contains(&(row, col))
+    }
+
+    // Accept: A player agrees to the current dead stones; once both agree the result is final
+    pub fn accept_score(&mut self, player: Stone) -> bool {
+        if self.phase != GamePhase::Scoring {
+            return false;
+        }
+        match player {
+            Stone::Black => self.black_accepted = true,
+            Stone::White => self.white_accepted = true,
+            Stone::Empty => return false,
+        }
+        if self.black_accepted && self.white_accepted {
+            let score = self.score();
+            self.finish(GameResult::from_scores(score.black, score.white));
+            self.final_score = Some(score);
+        }
+        true
+    }
+
+    // Accept: Check whether a player has accepted the current dead stones
+    pub fn has_accepted(&self, player: Stone) -> bool {
+        match player {
+            Stone::Black => self.black_accepted,
+            Stone::White => self.white_accepted,
+            Stone::Empty => false,
+        }
+    }
+
+    // Resume: Leave the scoring phase and continue play (the player who did not pass last moves)
+    pub fn resume_play(&mut self) -> bool {
+        if self.phase != GamePhase::Scoring {
+            return false;
+        }
+        self.clear_scoring();
+        self.game_over = false;
+        self.consecutive_passes = 0;
+        self.current_player = self.current_player.opponent();
+        if let Some(clock) = &mut self.clock {
+            clock.start(self.current_player);
+        }
+        true
+    }
+}
+
+impl GameState {
+    // Dead Stones: Points marked dead, in board order
+    pub fn dead_stones(&self) -> Vec<(usize, usize)> {
+        let mut points: Vec<(usize, usize)> = self.dead_stones.iter().copied().collect();
+        points.sort_unstable();
+        points
+    }
+
+    // Seki: Chains (dead stones removed) touching a shared liberty, sorted by their first point
+    // A shared liberty is a neutral point where playing would be self-atari for either color
+    pub fn seki_groups(&self) -> Vec<Vec<Point>> {
+        let board = self.scoring_board();
+        seki_chains(&board, &territory_map(&board, self.size))
+    }
+
+    // Final Score: Agreed scores (None until both players accept)
+    pub fn final_score(&self) -> Option<Score> {
+        self.final_score
+    }
+
+    // Scoring: Enter the scoring phase with no stones marked dead
+    pub(crate) fn start_scoring(&mut self) {
+        self.clear_scoring();
+        self.phase = GamePhase::Scoring;
+    }
+
+    // Scoring: Drop dead marks, acceptance and the result of a finished game, back to playing
+    pub(crate) fn clear_scoring(&mut self) {
+        if self.phase == GamePhase::Finished {
+            self.result = None;
+        }
+        self.final_score = None;
+        self.phase = GamePhase::Playing;
+        self.dead_stones.clear();
+        self.black_accepted = false;
+        self.white_accepted = false;
+    }
+
+    // Scoring Board: Board with dead stones removed
+    pub(crate) fn scoring_board(&self) -> Vec<Vec<Stone>> {
+        let mut board = self.board.clone();
+        for &(row, col) in &self.dead_stones {
+            board[row][col] = Stone::Empty;
+        }
+        board
+    }
+
+    // Territory: Owner of each point after removing dead stones
+    // Eyes of seki groups stay neutral when the ruleset does not count them
+    pub(crate) fn territory(&self) -> Vec<Vec<Stone>> {
+        let board = self.scoring_board();
+        let mut territory = territory_map(&board, self.size);
+        if !self.ruleset.counts_seki_eyes() {
+            let seki = seki_chains(&board, &territory);
+            clear_seki_eyes(&board, &mut territory, &seki);
+        }
+        territory
+    }
+
+    // Scoring View: Territory together with the seki chains, finding seki only once
+    pub(crate) fn territory_and_seki(&self) -> (Vec<Vec<Stone>>, Vec<Vec<Point>>) {
+        let board = self.scoring_board();
+        let mut territory = territory_map(&board, self.size);
+        let seki = seki_chains(&board, &territory);
+        if !self.ruleset.counts_seki_eyes() {
+            clear_seki_eyes(&board, &mut territory, &seki);
+        }
+        (territory, seki)
+    }
+
+    // Dead Stones: Number of a color's stones marked dead (prisoners for the opponent)
+    pub(crate) fn dead_count(&self, player: Stone) -> usize {
+        self.dead_stones.iter().filter(|&&(row, col)| self.board[row][col] == player).count()
+    }
+}
+
+// Seki: Chains touching a neutral point that would be self-atari for both colors, sorted by their first point
+fn seki_chains(board: &[Vec<Stone>], territory: &[Vec<Stone>]) -> Vec<Vec<Point>> {
+    let size = board.len();
+    let chains = Chains::from_board(board);
+    let mut seen = vec![vec![false; size]; size];
+    let mut groups = Vec::new();
+
+    for row in 0..size {
+        for col in 0..size {
+            if board[row][col] != Stone::Empty || territory[row][col] != Stone::Empty {
+                continue;
+            }
+            let shared = is_self_atari(&chains, size, row, col, Stone::Black)
+                && is_self_atari(&chains, size, row, col, Stone::White);
+            if !shared {
+                continue;
+            }
+            for (r, c) in neighbors(size, (row, col)) {
+                if board[r][c] == Stone::Empty || seen[r][c] {
+                    continue;
+                }
+                let group = chains.chain(r, c);
+                group.iter().for_each(|&(r, c)| seen[r][c] = true);
+                groups.push(group);
+            }
+        }
+    }
+
+    groups.sort_unstable();
+    groups
+}
+
+// Eyes: Clear every owned empty region touching a seki stone
+fn clear_seki_eyes(board: &[Vec<Stone>], territory: &mut [Vec<Stone>], seki: &[Vec<Point>]) {
+    let size = board.len();
+    for &(row, col) in seki.iter().flatten() {
+        let mut stack: Vec<Point> = neighbors(size, (row, col)).collect();
+        while let Some((r, c)) = stack.pop() {
+            if board[r][c] != Stone::Empty || territory[r][c] == Stone::Empty {
+                continue;
+            }
+            territory[r][c] = Stone::Empty;
+            stack.extend(neighbors(size, (r, c)));
+        }
+    }
+}
+
+// Self-Atari: Whether playing stone at an empty point captures nothing and leaves its chain with at most one liberty
+fn is_self_atari(chains: &Chains, size: usize, row: usize, col: usize, stone: Stone) -> bool {
+    let captures = neighbors(size, (row, col)).any(|(r, c)| {
+        let other = chains.stone(r, c);
+        other != Stone::Empty && other != stone && chains.liberties(r, c) == 1
+    });
+    !captures && chains.liberties_after(row, col, stone) <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Seki: Two eyeless chains in the top corners sharing the liberty (0, 2)
+    fn seki() -> GameState {
+        let mut stones = Vec::new();
+        for point in [(0, 0), (0, 1), (1, 0), (1, 1), (2, 3), (2, 4)] {
+            stones.push((point.0, point.1, Stone::Black));
+        }
+        for point in [(0, 3), (0, 4), (1, 3), (1, 4), (2, 0), (2, 1), (2, 2)] {
+            stones.push((point.0, point.1, Stone::White));
+        }
+        let mut game = GameState::with_size(5);
+        game.apply_setup(&stones, Stone::Black);
+        game
+    }
+
+    #[test]
+    fn shared_liberty_marks_both_chains_as_seki() {
+        let game = seki();
+        let black = vec![(0, 0), (0, 1), (1, 0), (1, 1)];
+        let white = vec![(0, 3), (0, 4), (1, 3), (1, 4)];
+        assert_eq!(game.seki_groups(), vec![black, white]);
+    }
+
+    #[test]
+    fn board_view_shows_seki_only_while_scoring() {
+        let mut game = seki();
+        assert!(game.board_view().intersections.iter().all(|i| !i.is_seki));
+        game.pass();
+        game.pass();
+        assert_eq!(game.phase(), GamePhase::Scoring);
+        let view = game.board_view();
+        let seki: Vec<(usize, usize)> =
+            view.intersections.iter().filter(|i| i.is_seki).map(|i| (i.row, i.col)).collect();
+        assert_eq!(seki, vec![(0, 0), (0, 1), (0, 3), (0, 4), (1, 0), (1, 1), (1, 3), (1, 4)]);
+        assert_eq!(view.intersections[2].territory, 0);
+    }
+
+    // Dead Stone: White wall on column 2 with a lone Black stone at (2, 4) inside White's area, marked dead
+    fn dead_stone_counted() -> GameState {
+        let mut stones: Vec<(usize, usize, Stone)> = (0..5).map(|row| (row, 1, Stone::Black)).collect();
+        stones.extend((0..5).map(|row| (row, 2, Stone::White)));
+        stones.push((2, 4, Stone::Black));
+        let mut game = GameState::with_size(5);
+        game.apply_setup(&stones, Stone::Black);
+        game.pass();
+        game.pass();
+        assert!(game.toggle_dead_group(2, 4));
+        game
+    }
+
+    #[test]
+    fn accepting_keeps_dead_stones_in_the_final_count() {
+        let mut game = dead_stone_counted();
+        let agreed = game.score();
+        assert!(game.accept_score(Stone::Black));
+        assert!(game.accept_score(Stone::White));
+
+        assert_eq!(game.phase(), GamePhase::Finished);
+        assert_eq!(game.final_score(), Some(agreed));
+        assert_eq!(game.score(), agreed);
+        assert!(game.is_dead(2, 4));
+        let view = game.board_view();
+        assert!(view.intersections[2 * 5 + 4].is_dead);
+        assert_eq!(view.intersections[2 * 5 + 3].territory, Stone::White.code());
+        assert_eq!(view.intersections[2 * 5 + 4].territory, Stone::White.code());
+    }
+
+    #[test]
+    fn resigning_while_counting_drops_dead_stones() {
+        let mut game = dead_stone_counted();
+        assert!(game.resign(Stone::Black));
+        assert_eq!(game.phase(), GamePhase::Finished);
+        assert_eq!(game.final_score(), None);
+        assert!(!game.is_dead(2, 4));
+    }
+}
@@ -0,0 +1,563 @@
+// Imports: WebAssembly bindings, error formatting, and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use std::fmt;
+
+use crate::{
+    Chains, GameResult, GameState, MoveRecord, PositionHistory, Ruleset, Stone, MAX_BOARD_SIZE, MIN_BOARD_SIZE,
+};
+
+// Enum: Errors raised while reading an SGF record
+#[derive(Debug, Clone, PartialEq)]
+pub enum SgfError {
+    UnexpectedEnd,
+    UnexpectedChar { position: usize, found: char },
+    MissingGameTree,
+    UnsupportedGame(String),
+    InvalidBoardSize(String),
+    InvalidNumber { property: String, value: String },
+    InvalidPoint(String),
+    IllegalMove { move_number: usize, value: String },
+    UnsupportedProperty(String),
+}
+
+impl fmt::Display for SgfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SgfError::UnexpectedEnd => write!(f, "unexpected end of SGF input"),
+            SgfError::UnexpectedChar { position, found } => {
+                write!(f, "unexpected character '{}' at byte {}", found, position)
+            }
+            SgfError::MissingGameTree => write!(f, "no game tree found (expected '(;')"),
+            SgfError::UnsupportedGame(gm) => write!(f, "GM[{}] is not a Go record", gm),
+            SgfError::InvalidBoardSize(sz) => {
+                write!(f, "board size SZ[{}] must be square and {}-{}", sz, MIN_BOARD_SIZE, MAX_BOARD_SIZE)
+            }
+            SgfError::InvalidNumber { property, value } => {
+                write!(f, "{}[{}] is not a valid number", property, value)
+            }
+            SgfError::InvalidPoint(point) => write!(f, "[{}] is not a point on the board", point),
+            SgfError::IllegalMove { move_number, value } => {
+                write!(f, "move {} [{}] is illegal", move_number, value)
+            }
+            SgfError::UnsupportedProperty(id) => {
+                write!(f, "{} is only supported in the root node", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SgfError {}
+
+// Struct: One SGF node - property identifiers with their raw (unescaped) values
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SgfNode {
+    pub properties: Vec<(String, Vec<String>)>,
+}
+
+impl SgfNode {
+    // Lookup: First value of a property
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.get_all(id).first().map(|v| v.as_str())
+    }
+
+    // Lookup: All values of a property (empty if absent)
+    pub fn get_all(&self, id: &str) -> &[String] {
+        self.properties
+            .iter()
+            .find(|(key, _)| key == id)
+            .map(|(_, values)| values.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+// Struct: SGF game tree - its sequences of nodes kept flat, each followed by its variations
+// (indexes into `sequences`), so deeply nested records need no recursion to read, walk or drop
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SgfTree {
+    pub sequences: Vec<SgfSequence>, // The root sequence first
+}
+
+// Struct: One "(...)" of a game tree - its nodes and the variations that follow them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SgfSequence {
+    pub nodes: Vec<SgfNode>,
+    pub variations: Vec<usize>,
+}
+
+impl SgfTree {
+    // Root: First node of the root sequence (game information and setup)
+    pub fn root(&self) -> &SgfNode {
+        &self.sequences[0].nodes[0]
+    }
+}
+
+// Parser: Reader over the SGF text
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SgfError> {
+        self.skip_whitespace();
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(SgfError::UnexpectedChar { position: self.pos - c.len_utf8(), found: c }),
+            None => Err(SgfError::UnexpectedEnd),
+        }
+    }
+
+    // Tree: "(" Sequence GameTree* ")" - nested game trees are tracked on an explicit stack
+    fn tree(&mut self) -> Result<SgfTree, SgfError> {
+        self.expect('(')?;
+        let mut tree = SgfTree { sequences: vec![SgfSequence::default()] };
+        let mut open = vec![0]; // Sequences whose ")" is still to come, innermost last
+        while let Some(&current) = open.last() {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(';') => {
+                    self.bump();
+                    let node = self.node()?;
+                    tree.sequences[current].nodes.push(node);
+                }
+                Some('(') => {
+                    self.bump();
+                    let variation = tree.sequences.len();
+                    tree.sequences.push(SgfSequence::default());
+                    tree.sequences[current].variations.push(variation);
+                    open.push(variation);
+                }
+                Some(')') => {
+                    self.bump();
+                    if tree.sequences[current].nodes.is_empty() {
+                        return Err(SgfError::MissingGameTree);
+                    }
+                    open.pop();
+                }
+                Some(c) => return Err(SgfError::UnexpectedChar { position: self.pos, found: c }),
+                None => return Err(SgfError::UnexpectedEnd),
+            }
+        }
+        Ok(tree)
+    }
+
+    // Node: Properties up to the next ';', '(' or ')'
+    fn node(&mut self) -> Result<SgfNode, SgfError> {
+        let mut node = SgfNode::default();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    // Identifier: FF[3] allowed lowercase letters, only the uppercase ones count
+                    let mut id = String::new();
+                    while let Some(c) = self.peek().filter(|c| c.is_ascii_alphabetic()) {
+                        if c.is_ascii_uppercase() {
+                            id.push(c);
+                        }
+                        self.bump();
+                    }
+                    let mut values = Vec::new();
+                    self.skip_whitespace();
+                    while self.peek() == Some('[') {
+                        values.push(self.value()?);
+                        self.skip_whitespace();
+                    }
+                    if values.is_empty() {
+                        return match self.peek() {
+                            Some(c) => Err(SgfError::UnexpectedChar { position: self.pos, found: c }),
+                            None => Err(SgfError::UnexpectedEnd),
+                        };
+                    }
+                    node.properties.push((id, values));
+                }
+                _ => return Ok(node),
+            }
+        }
+    }
+
+    // Value: "[" text "]" with '\' escapes and escaped line breaks removed
+    fn value(&mut self) -> Result<String, SgfError> {
+        self.expect('[')?;
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some('\\') => match self.bump() {
+                    Some('\n') => {}
+                    Some('\r') => {
+                        if self.peek() == Some('\n') {
+                            self.bump();
+                        }
+                    }
+                    Some(c) => value.push(c),
+                    None => return Err(SgfError::UnexpectedEnd),
+                },
+                Some(']') => return Ok(value),
+                Some(c) => value.push(c),
+                None => return Err(SgfError::UnexpectedEnd),
+            }
+        }
+    }
+}
+
+// Parse: Read every game tree in a collection
+pub(crate) fn parse(input: &str) -> Result<Vec<SgfTree>, SgfError> {
+    let mut parser = Parser { input, pos: 0 };
+    let mut trees = Vec::new();
+    loop {
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some('(') => trees.push(parser.tree()?),
+            Some(c) if trees.is_empty() => {
+                return Err(SgfError::UnexpectedChar { position: parser.pos, found: c })
+            }
+            _ => break,
+        }
+    }
+    if trees.is_empty() {
+        return Err(SgfError::MissingGameTree);
+    }
+    Ok(trees)
+}
+
+// Escape: Quote ']' and '\' inside a property value
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == ']' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Point: SGF coordinate letters (column first, 'a' = top-left)
+pub(crate) fn format_point(row: usize, col: usize) -> String {
+    let letter = |i: usize| char::from(b'a' + i as u8);
+    format!("{}{}", letter(col), letter(row))
+}
+
+// Point: Parse SGF coordinate letters into (row, col); None for a pass ("" or "tt" on <= 19x19)
+pub(crate) fn parse_point(value: &str, size: usize) -> Result<Option<(usize, usize)>, SgfError> {
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || (value == "tt" && size <= 19) {
+        return Ok(None);
+    }
+    let index = |b: u8| match b {
+        b'a'..=b'z' => Some((b - b'a') as usize),
+        b'A'..=b'Z' => Some((b - b'A') as usize + 26),
+        _ => None,
+    };
+    match (bytes.len(), index(bytes[0]), bytes.get(1).and_then(|&b| index(b))) {
+        (2, Some(col), Some(row)) if row < size && col < size => Ok(Some((row, col))),
+        _ => Err(SgfError::InvalidPoint(value.to_string())),
+    }
+}
+
+// Points: Expand a point list, including compressed "aa:cc" rectangles
+fn parse_point_list(values: &[String], size: usize) -> Result<Vec<(usize, usize)>, SgfError> {
+    let mut points = Vec::new();
+    for value in values {
+        let (from, to) = value.split_once(':').unwrap_or((value, value));
+        let invalid = || SgfError::InvalidPoint(value.clone());
+        let (r1, c1) = parse_point(from, size)?.ok_or_else(invalid)?;
+        let (r2, c2) = parse_point(to, size)?.ok_or_else(invalid)?;
+        for row in r1.min(r2)..=r1.max(r2) {
+            for col in c1.min(c2)..=c1.max(c2) {
+                points.push((row, col));
+            }
+        }
+    }
+    Ok(points)
+}
+
+// Number: Parse a numeric property value
+fn parse_number<T: std::str::FromStr>(id: &str, value: &str) -> Result<T, SgfError> {
+    value.trim().parse().map_err(|_| SgfError::InvalidNumber {
+        property: id.to_string(),
+        value: value.to_string(),
+    })
+}
+
+// Ruleset: Map SGF RU values (case-insensitive, common aliases) to a ruleset
+pub(crate) fn parse_ruleset(value: &str) -> Option<Ruleset> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "japanese" | "jp" => Some(Ruleset::Japanese),
+        "chinese" | "cn" => Some(Ruleset::Chinese),
+        "aga" => Some(Ruleset::Aga),
+        "nz" | "new zealand" | "newzealand" => Some(Ruleset::NewZealand),
+        "tromp-taylor" | "tromptaylor" | "tromp taylor" => Some(Ruleset::TrompTaylor),
+        "ing" | "goe" => Some(Ruleset::Ing),
+        _ => None,
+    }
+}
+
+// Root: Game state from the root node - size, ruleset, komi, game info, setup stones, player to move
+pub(crate) fn root_state(root: &SgfNode) -> Result<GameState, SgfError> {
+    if let Some(gm) = root.get("GM") {
+        if gm.trim() != "1" {
+            return Err(SgfError::UnsupportedGame(gm.to_string()));
+        }
+    }
+
+    // Root: Board size, ruleset and komi
+    let size = match root.get("SZ") {
+        Some(sz) => {
+            let (w, h) = sz.split_once(':').unwrap_or((sz, sz));
+            let invalid = || SgfError::InvalidBoardSize(sz.to_string());
+            let w: usize = w.trim().parse().map_err(|_| invalid())?;
+            let h: usize = h.trim().parse().map_err(|_| invalid())?;
+            if w != h || !(MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&w) {
+                return Err(invalid());
+            }
+            w
+        }
+        None => 19,
+    };
+    let ruleset = root.get("RU").and_then(parse_ruleset).unwrap_or_default();
+    let mut state = GameState::with_rules(size, ruleset);
+    if let Some(km) = root.get("KM") {
+        state.komi = parse_number("KM", km)?;
+    }
+    if let Some(ha) = root.get("HA") {
+        state.handicap = parse_number("HA", ha)?;
+    }
+
+    // Root: Game information
+    state.black_name = root.get("PB").map(str::to_string);
+    state.white_name = root.get("PW").map(str::to_string);
+    state.result = root.get("RE").and_then(GameResult::parse);
+    state.comment = root.get("C").map(str::to_string);
+
+    // Root: Setup stones and player to move
+    let mut setup = Vec::new();
+    for (id, stone) in [("AB", Stone::Black), ("AW", Stone::White), ("AE", Stone::Empty)] {
+        for (row, col) in parse_point_list(root.get_all(id), size)? {
+            setup.push((row, col, stone));
+        }
+    }
+    let first_player = match root.get("PL") {
+        Some("W") | Some("w") => Stone::White,
+        Some(_) => Stone::Black,
+        None if state.handicap > 1 => Stone::White,
+        None => Stone::Black,
+    };
+    state.apply_setup(&setup, first_player);
+
+    Ok(state)
+}
+
+// Type: A move read from a node - color and point (None for pass)
+pub(crate) type SgfMove = (Stone, Option<(usize, usize)>);
+
+// Node: Move in a node, if any; setup is only accepted in the root
+pub(crate) fn read_move(node: &SgfNode, size: usize, is_root: bool) -> Result<Option<SgfMove>, SgfError> {
+    for id in ["AB", "AW", "AE"] {
+        if !is_root && !node.get_all(id).is_empty() {
+            return Err(SgfError::UnsupportedProperty(id.to_string()));
+        }
+    }
+    let played = match (node.get("B"), node.get("W")) {
+        (Some(value), _) => Some((Stone::Black, value)),
+        (None, Some(value)) => Some((Stone::White, value)),
+        (None, None) => None,
+    };
+    match played {
+        Some((player, value)) => Ok(Some((player, parse_point(value, size)?))),
+        None => Ok(None),
+    }
+}
+
+// Error: Illegal move n at a point (or pass)
+pub(crate) fn illegal_move(move_number: usize, point: Option<(usize, usize)>) -> SgfError {
+    SgfError::IllegalMove {
+        move_number,
+        value: point.map_or(String::new(), |(row, col)| format_point(row, col)),
+    }
+}
+
+// Move: Property text for a recorded move, e.g. ";B[pd]C[...]"
+pub(crate) fn format_move(record: &MoveRecord) -> String {
+    let color = if record.player == Stone::White { "W" } else { "B" };
+    let point = record.point.map_or(String::new(), |(row, col)| format_point(row, col));
+    let mut text = format!(";{}[{}]", color, point);
+    if let Some(comment) = &record.comment {
+        text.push_str(&format!("C[{}]", escape(comment)));
+    }
+    text
+}
+
+impl GameState {
+    // Import: Build a game from the main line of the first game tree in an SGF record
+    pub fn from_sgf(sgf: &str) -> Result<GameState, SgfError> {
+        let trees = parse(sgf)?;
+        let tree = &trees[0];
+        let mut state = root_state(tree.root())?;
+
+        // Main Line: Follow the first variation at every branch
+        let mut branch = &tree.sequences[0];
+        let mut nodes: Vec<&SgfNode> = branch.nodes.iter().collect();
+        while let Some(&next) = branch.variations.first() {
+            branch = &tree.sequences[next];
+            nodes.extend(branch.nodes.iter());
+        }
+
+        for (index, node) in nodes.into_iter().enumerate() {
+            if let Some((player, point)) = read_move(node, state.size, index == 0)? {
+                if !state.play_as(player, point) {
+                    return Err(illegal_move(state.move_number + 1, point));
+                }
+            }
+            if let Some(comment) = node.get("C").filter(|_| index > 0) {
+                state.set_comment(comment);
+            }
+        }
+
+        Ok(state)
+    }
+
+    // Root Board: Board before any recorded move (setup and handicap stones only)
+    pub(crate) fn root_board(&self) -> Vec<Vec<Stone>> {
+        let mut board = self.board.clone();
+        for record in self.moves[..self.move_number].iter().rev() {
+            record.unapply(&mut board);
+        }
+        board
+    }
+
+    // Root Properties: Game information, setup stones and player to move as SGF text
+    pub(crate) fn sgf_root_properties(&self) -> String {
+        let mut sgf = format!(
+            "FF[4]GM[1]CA[UTF-8]AP[go_game:{}]SZ[{}]KM[{}]RU[{}]",
+            env!("CARGO_PKG_VERSION"),
+            self.size,
+            self.komi,
+            self.ruleset.name()
+        );
+        if self.handicap > 0 {
+            sgf.push_str(&format!("HA[{}]", self.handicap));
+        }
+        let result = self.result.map(|r| r.to_string());
+        let info = [
+            ("PB", &self.black_name),
+            ("PW", &self.white_name),
+            ("RE", &result),
+            ("C", &self.comment),
+        ];
+        for (id, value) in info {
+            if let Some(value) = value {
+                sgf.push_str(&format!("{}[{}]", id, escape(value)));
+            }
+        }
+
+        // Setup: AB/AW for stones present before the first move
+        let root = self.root_board();
+        for (id, stone) in [("AB", Stone::Black), ("AW", Stone::White)] {
+            let points: Vec<String> = (0..self.size)
+                .flat_map(|row| (0..self.size).map(move |col| (row, col)))
+                .filter(|&(row, col)| root[row][col] == stone)
+                .map(|(row, col)| format!("[{}]", format_point(row, col)))
+                .collect();
+            if !points.is_empty() {
+                sgf.push_str(id);
+                sgf.push_str(&points.concat());
+            }
+        }
+        let first_player = self.moves.first().map_or(self.current_player, |m| m.player);
+        if first_player == Stone::White {
+            sgf.push_str("PL[W]");
+        }
+        sgf
+    }
+
+    // Setup: Place stones before the first move and set the player to move
+    pub(crate) fn apply_setup(&mut self, stones: &[(usize, usize, Stone)], player: Stone) {
+        for &(row, col, stone) in stones {
+            if row < self.size && col < self.size {
+                self.board[row][col] = stone;
+            }
+        }
+        self.chains = Chains::from_board(&self.board);
+        self.current_player = player;
+        self.position_history = PositionHistory::new(self.chains.hash(), player);
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameState {
+    // Export: Serialize the game (main line up to the current move) as an FF[4] SGF record
+    pub fn to_sgf(&self) -> String {
+        let mut sgf = String::from("(;");
+        sgf.push_str(&self.sgf_root_properties());
+        for record in &self.moves[..self.move_number] {
+            sgf.push('\n');
+            sgf.push_str(&format_move(record));
+        }
+        sgf.push_str(")\n");
+        sgf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Nested: A root node followed by depth game trees, each nested in the one before
+    fn nested(depth: usize, close: bool) -> String {
+        let closing = if close { ")".repeat(depth + 1) } else { String::new() };
+        format!("(;SZ[9]{}{}", "(;".repeat(depth), closing)
+    }
+
+    #[test]
+    fn parses_variations_in_order() {
+        let trees = parse("(;SZ[9];B[aa](;W[bb];B[cc])(;W[dd]))").unwrap();
+        let tree = &trees[0];
+        assert_eq!(tree.root().get("SZ"), Some("9"));
+        assert_eq!(tree.sequences[0].nodes.len(), 2);
+        assert_eq!(tree.sequences[0].variations, vec![1, 2]);
+        assert_eq!(tree.sequences[1].nodes[0].get("W"), Some("bb"));
+        assert_eq!(tree.sequences[1].nodes[1].get("B"), Some("cc"));
+        assert_eq!(tree.sequences[2].nodes[0].get("W"), Some("dd"));
+    }
+
+    #[test]
+    fn deeply_nested_record_parses_without_recursion() {
+        let trees = parse(&nested(200_000, true)).unwrap();
+        assert_eq!(trees[0].sequences.len(), 200_001);
+        let state = GameState::from_sgf(&nested(200_000, true)).unwrap();
+        assert_eq!(state.size, 9);
+    }
+
+    #[test]
+    fn deeply_nested_record_reports_errors() {
+        assert_eq!(parse(&nested(200_000, false)), Err(SgfError::UnexpectedEnd));
+        assert_eq!(parse(&format!("(;{}", "(".repeat(200_000))), Err(SgfError::UnexpectedEnd));
+        assert_eq!(parse("(;SZ[9]())"), Err(SgfError::MissingGameTree));
+    }
+
+    #[test]
+    fn main_line_follows_first_variations() {
+        let state = GameState::from_sgf("(;SZ[9](;B[aa](;W[bb])(;W[cc]))(;B[dd]))").unwrap();
+        assert_eq!(state.board[0][0], Stone::Black);
+        assert_eq!(state.board[1][1], Stone::White);
+        assert_eq!(state.board[2][2], Stone::Empty);
+        assert_eq!(state.board[3][3], Stone::Empty);
+    }
+}
@@ -0,0 +1,405 @@
+// Imports: WebAssembly bindings, serialization, and game types
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+
+use crate::sgf::{self, SgfError, SgfTree};
+use crate::{GameState, MoveRecord, Ruleset, Stone};
+
+// Struct: One node of the game tree - the move that led here and its state delta
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeNode {
+    pub id: usize,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>, // First child is the main line
+    pub record: Option<MoveRecord>, // None for the root (and for deleted nodes)
+}
+
+// Struct: Game tree with variations - node arena plus the game state at the current node
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct GameTree {
+    nodes: Vec<TreeNode>,
+    current: usize,
+    state: GameState, // Moves recorded in the state are the path from the root to `current`
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameTree {
+    // Constructor: Create empty tree on an NxN board under the given ruleset
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(size: usize, ruleset: Ruleset) -> GameTree {
+        GameTree::from_state(GameState::with_rules(size, ruleset))
+    }
+
+    // Getters: Current node id and the game state at it (cloned for JavaScript)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn node_id(&self) -> usize {
+        self.current
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = state))]
+    pub fn state_js(&self) -> GameState {
+        self.state.clone()
+    }
+
+    // Play: Play at (row, col) - follows an existing child with that move or adds a new variation
+    pub fn play(&mut self, row: usize, col: usize) -> bool {
+        self.play_move(self.state.current_player, Some((row, col)))
+    }
+
+    // Pass: Pass at the current node - follows an existing pass child or adds a new variation
+    pub fn pass(&mut self) -> bool {
+        self.play_move(self.state.current_player, None)
+    }
+
+    // Walk: Parent of a node (None for the root)
+    pub fn parent_of(&self, id: usize) -> Option<usize> {
+        self.nodes.get(id).and_then(|node| node.parent)
+    }
+
+    // Walk: Children of a node, main line first
+    pub fn children_of(&self, id: usize) -> Vec<usize> {
+        self.nodes.get(id).map_or(Vec::new(), |node| node.children.clone())
+    }
+
+    // Back: Go to the parent node
+    pub fn back(&mut self) -> bool {
+        match self.nodes[self.current].parent {
+            Some(parent) => {
+                self.state.undo();
+                self.current = parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Forward: Go to the first (main line) child
+    pub fn forward(&mut self) -> bool {
+        match self.nodes[self.current].children.first() {
+            Some(&child) => self.descend(child),
+            None => false,
+        }
+    }
+
+    // Siblings: Switch to the next variation of the current move
+    pub fn next_variation(&mut self) -> bool {
+        self.switch_sibling(1)
+    }
+
+    // Siblings: Switch to the previous variation of the current move
+    pub fn previous_variation(&mut self) -> bool {
+        self.switch_sibling(-1)
+    }
+
+    // Go To: Navigate to any node in the tree
+    pub fn goto_node(&mut self, id: usize) -> bool {
+        let target = match self.path_to(id) {
+            Some(path) => path,
+            None => return false,
+        };
+        let here = self.path_to(self.current).unwrap_or_default();
+        let common = here.iter().zip(&target).take_while(|(a, b)| a == b).count();
+        for _ in common..here.len() {
+            self.back();
+        }
+        for &node in &target[common..] {
+            if !self.descend(node) {
+                return false;
+            }
+        }
+        true
+    }
+
+    // Promote: Make the path to the current node the main line at every branch above it
+    pub fn promote_variation(&mut self) {
+        let mut node = self.current;
+        while let Some(parent) = self.nodes[node].parent {
+            let siblings = &mut self.nodes[parent].children;
+            if let Some(index) = siblings.iter().position(|&child| child == node) {
+                let id = siblings.remove(index);
+                siblings.insert(0, id);
+            }
+            node = parent;
+        }
+    }
+
+    // Delete: Remove the current node and everything below it, then go to its parent
+    pub fn delete_branch(&mut self) -> bool {
+        let parent = match self.nodes[self.current].parent {
+            Some(parent) => parent,
+            None => return false,
+        };
+        let removed = self.current;
+        self.back();
+        self.nodes[parent].children.retain(|&child| child != removed);
+
+        // Detach: Deleted nodes stay in the arena but are unreachable
+        let mut stack = vec![removed];
+        while let Some(id) = stack.pop() {
+            let node = &mut self.nodes[id];
+            stack.append(&mut node.children);
+            node.parent = None;
+            node.record = None;
+        }
+        true
+    }
+
+    // Comment: Get/set the comment on the current node
+    pub fn get_comment(&self) -> Option<String> {
+        self.state.get_comment()
+    }
+
+    pub fn set_comment(&mut self, text: &str) {
+        self.state.set_comment(text);
+        if let Some(record) = &mut self.nodes[self.current].record {
+            record.comment = self.state.get_comment();
+        }
+    }
+
+    // Export: Serialize the whole tree, variations included, as an FF[4] SGF record
+    pub fn to_sgf(&self) -> String {
+        let mut sgf = String::from("(;");
+        sgf.push_str(&self.state.sgf_root_properties());
+        self.write_sequence(0, &mut sgf);
+        sgf.push_str(")\n");
+        sgf
+    }
+}
+
+impl GameTree {
+    // Create: Tree whose main line is the state's recorded moves, positioned at the state's current move
+    pub fn from_state(mut state: GameState) -> GameTree {
+        let move_number = state.move_number;
+        state.goto_move(0);
+        let records = std::mem::take(&mut state.moves);
+        let mut tree = GameTree {
+            nodes: vec![TreeNode { id: 0, parent: None, children: Vec::new(), record: None }],
+            current: 0,
+            state,
+        };
+        for record in &records {
+            if !tree.play_move(record.player, record.point) {
+                break;
+            }
+            if let Some(comment) = &record.comment {
+                tree.set_comment(comment);
+            }
+        }
+        tree.goto_node(0);
+        for _ in 0..move_number {
+            tree.forward();
+        }
+        tree
+    }
+
+    // Import: Build a tree from the first game tree of an SGF record, keeping all variations
+    pub fn from_sgf(sgf: &str) -> Result<GameTree, SgfError> {
+        let trees = sgf::parse(sgf)?;
+        let state = sgf::root_state(trees[0].root())?;
+        let mut tree = GameTree {
+            nodes: vec![TreeNode { id: 0, parent: None, children: Vec::new(), record: None }],
+            current: 0,
+            state,
+        };
+        tree.import(&trees[0])?;
+        tree.goto_node(0);
+        Ok(tree)
+    }
+
+    // State: Game state at the current node
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    // Node: A node by id (None if unknown)
+    pub fn node(&self, id: usize) -> Option<&TreeNode> {
+        self.nodes.get(id)
+    }
+
+    // Nodes: All nodes, including detached (deleted) ones
+    pub fn nodes(&self) -> &[TreeNode] {
+        &self.nodes
+    }
+
+    // Play: Follow a matching child or add a new child (variation) with this move
+    fn play_move(&mut self, player: Stone, point: Option<(usize, usize)>) -> bool {
+        let existing = self.nodes[self.current].children.iter().copied().find(|&child| {
+            self.nodes[child]
+                .record
+                .as_ref()
+                .is_some_and(|r| r.player == player && r.point == point)
+        });
+        if let Some(child) = existing {
+            return self.descend(child);
+        }
+
+        if !self.state.play_as(player, point) {
+            return false;
+        }
+        let id = self.nodes.len();
+        let record = self.state.moves[self.state.move_number - 1].clone();
+        self.nodes.push(TreeNode { id, parent: Some(self.current), children: Vec::new(), record: Some(record) });
+        self.nodes[self.current].children.push(id);
+        self.current = id;
+        true
+    }
+
+    // Descend: Step into a child node by replaying its move
+    fn descend(&mut self, child: usize) -> bool {
+        if self.nodes[child].parent != Some(self.current) {
+            return false;
+        }
+        let record = match &self.nodes[child].record {
+            Some(record) => record.clone(),
+            None => return false,
+        };
+        self.state.moves.truncate(self.state.move_number);
+        if !self.state.replay(&record) {
+            return false;
+        }
+        self.current = child;
+        true
+    }
+
+    // Siblings: Move by offset among the current node's siblings
+    fn switch_sibling(&mut self, offset: isize) -> bool {
+        let parent = match self.nodes[self.current].parent {
+            Some(parent) => parent,
+            None => return false,
+        };
+        let siblings = &self.nodes[parent].children;
+        let index = siblings.iter().position(|&child| child == self.current).unwrap_or(0) as isize;
+        let target = match siblings.get((index + offset) as usize) {
+            Some(&target) if index + offset >= 0 => target,
+            _ => return false,
+        };
+        self.back();
+        self.descend(target)
+    }
+
+    // Path: Node ids from the root to a node (None if deleted or unknown)
+    fn path_to(&self, id: usize) -> Option<Vec<usize>> {
+        let mut path = vec![id];
+        let mut node = self.nodes.get(id)?;
+        while let Some(parent) = node.parent {
+            path.push(parent);
+            node = &self.nodes[parent];
+        }
+        if *path.last()? != 0 {
+            return None;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // Import: Add a parsed SGF tree below the current node - sequences still to add wait on an explicit
+    // stack with the node they branch from, so deeply nested records need no recursion
+    fn import(&mut self, tree: &SgfTree) -> Result<(), SgfError> {
+        let mut pending = vec![(0, self.current)];
+        while let Some((sequence, from)) = pending.pop() {
+            if self.nodes[self.current].parent == Some(from) {
+                self.back();
+            } else if from != self.current {
+                self.goto_node(from);
+            }
+            let branch = &tree.sequences[sequence];
+            for (index, node) in branch.nodes.iter().enumerate() {
+                let root_node = sequence == 0 && index == 0;
+                if let Some((player, point)) = sgf::read_move(node, self.state.size, root_node)? {
+                    if !self.play_move(player, point) {
+                        return Err(sgf::illegal_move(self.state.move_number + 1, point));
+                    }
+                }
+                if let Some(comment) = node.get("C").filter(|_| !root_node) {
+                    self.set_comment(comment);
+                }
+            }
+            // Variations: Pushed last-first so the main line is added first
+            pending.extend(branch.variations.iter().rev().map(|&variation| (variation, self.current)));
+        }
+        Ok(())
+    }
+
+    // Export: Write a node's move sequence, opening "(...)" for each variation at a branch
+    // Variations still to write wait on an explicit stack, so deep trees need no recursion
+    fn write_sequence(&self, mut id: usize, sgf: &mut String) {
+        enum Step {
+            Variation(usize),
+            Close,
+        }
+        let mut pending = Vec::new();
+        loop {
+            loop {
+                let node = &self.nodes[id];
+                if let Some(record) = &node.record {
+                    if !sgf.ends_with('(') {
+                        sgf.push('\n');
+                    }
+                    sgf.push_str(&sgf::format_move(record));
+                }
+                match node.children.len() {
+                    0 => break,
+                    1 => id = node.children[0],
+                    _ => {
+                        for &child in node.children.iter().rev() {
+                            pending.push(Step::Close);
+                            pending.push(Step::Variation(child));
+                        }
+                        break;
+                    }
+                }
+            }
+
+            // Next: Close finished variations, then open the next one (done when none is left)
+            loop {
+                match pending.pop() {
+                    Some(Step::Close) => sgf.push(')'),
+                    Some(Step::Variation(child)) => {
+                        sgf.push_str("\n(");
+                        id = child;
+                        break;
+                    }
+                    None => return,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORD: &str = "(;SZ[9];B[aa](;W[bb];B[cc](;W[dd])(;W[ee]C[x]))(;W[ff]))";
+
+    #[test]
+    fn variations_survive_a_round_trip() {
+        let tree = GameTree::from_sgf(RECORD).unwrap();
+        assert_eq!(tree.children_of(1).len(), 2);
+        let sgf = tree.to_sgf();
+        assert!(sgf.ends_with(";B[aa]\n(;W[bb]\n;B[cc]\n(;W[dd])\n(;W[ee]C[x]))\n(;W[ff]))\n"));
+        assert_eq!(GameTree::from_sgf(&sgf).unwrap().to_sgf(), sgf);
+    }
+
+    #[test]
+    fn deeply_nested_record_imports_and_exports() {
+        // Nested: Every move in its own game tree, 10000 deep
+        let moves: String = (0..10_000).map(|i| if i % 2 == 0 { "(;B[]" } else { "(;W[]" }).collect();
+        let nested = format!("(;SZ[9]{}{})", moves, ")".repeat(10_000));
+        let tree = GameTree::from_sgf(&nested).unwrap();
+        assert_eq!(tree.nodes().len(), 10_001);
+
+        // Branching: Two variations at every move, each branch nested in the one before
+        let mut tree = GameTree::new(9, Ruleset::Japanese);
+        for _ in 0..10_000 {
+            tree.play(0, 0);
+            tree.back();
+            tree.pass();
+        }
+        let sgf = tree.to_sgf();
+        assert_eq!(sgf.matches('(').count(), 20_001);
+        assert_eq!(GameTree::from_sgf(&sgf).unwrap().nodes().len(), 20_001);
+    }
+}
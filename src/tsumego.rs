@@ -0,0 +1,320 @@
+// Imports: Serialization, hashing and game types
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::{Chains, GameState, Move, Point, Stone};
+
+// Constant: Positions searched per pass before the problem is given up as unsolved
+const SEARCH_LIMIT: usize = 200_000;
+
+// Enum: Fate of a group with the player to move starting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LifeStatus {
+    Alive,   // Lives even if the attacker wins every ko
+    Dead,    // Is captured even if the defender wins every ko
+    Ko,      // Lives or dies depending on who wins a ko
+    Unknown, // Search limit reached
+}
+
+// Struct: Solved life-and-death problem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LifeSolution {
+    pub status: LifeStatus,
+    pub key_move: Option<Move>, // First move of the variation (None if unsolved or already pass-alive)
+    pub variation: Vec<Move>,   // Principal variation, starting with the player to move
+}
+
+// Struct: Search state - who fights, where moves may be played, and which side may ignore the ko ban
+// (standing for a side with unlimited ko threats)
+struct Solver {
+    target: Point,
+    defender: Stone,
+    region: Vec<Point>,
+    exempt: Stone,
+    table: HashMap<Key, (bool, Move)>, // Proven result (target lives) and the move that proves it
+    path: Vec<u64>,                    // Positions on the way from the root, to keep the search from cycling
+    cycled: bool,                      // A move was skipped as a repetition (the result depends on the path)
+    budget: usize,
+    max_depth: usize,
+}
+
+// Struct: Transposition table key - position, player to move, banned ko point and whether the last move passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    hash: u64,
+    to_move: Stone,
+    ko: Option<Point>,
+    passed: bool,
+}
+
+impl GameState {
+    // Solve Life: Read whether the chain at target lives, dies or depends on a ko, with the player to move
+    // starting; moves are only played on empty points of region (which should enclose the fight),
+    // both players may pass, and two passes in a row leave the chain alive. None if target is empty
+    pub fn solve_life(&self, target: Point, region: &[Point]) -> Option<LifeSolution> {
+        self.solve_life_within(target, region, SEARCH_LIMIT)
+    }
+
+    // Solve Within: Solve Life, giving up as Unknown after limit positions per pass
+    fn solve_life_within(&self, target: Point, region: &[Point], limit: usize) -> Option<LifeSolution> {
+        let (row, col) = target;
+        if row >= self.size || col >= self.size || self.board[row][col] == Stone::Empty {
+            return None;
+        }
+        let defender = self.board[row][col];
+        let attacker = defender.opponent();
+        let mut region: Vec<Point> =
+            region.iter().copied().filter(|&(r, c)| r < self.size && c < self.size).collect();
+        region.sort_unstable();
+        region.dedup();
+
+        // Settled: A pass-alive chain needs no reading
+        if self.pass_alive(defender).chains.iter().any(|chain| chain.contains(&target)) {
+            return Some(LifeSolution { status: LifeStatus::Alive, key_move: None, variation: Vec::new() });
+        }
+
+        // Ko: A point the player to move may not retake right away
+        let ko = region.iter().copied().find(|&(r, c)| {
+            self.board[r][c] == Stone::Empty
+                && Some(self.chains.probe(r, c, self.current_player).hash) == self.previous_hash
+        });
+
+        // Search: Once with unlimited ko threats for each side
+        let mut solvers = [
+            Solver::new(target, defender, &region, defender, limit),
+            Solver::new(target, defender, &region, attacker, limit),
+        ];
+        let [with_kos, without_kos] =
+            solvers.each_mut().map(|solver| solver.lives(&self.chains, self.current_player, ko, false, 0));
+        let status = match (with_kos, without_kos) {
+            (_, Some(true)) => LifeStatus::Alive,
+            (Some(false), _) => LifeStatus::Dead,
+            (Some(true), Some(false)) => LifeStatus::Ko,
+            _ => LifeStatus::Unknown,
+        };
+
+        // Variation: From the search where the outcome is proven (the player to move's ko search for a ko)
+        let solver = match status {
+            LifeStatus::Alive => &solvers[1],
+            LifeStatus::Dead => &solvers[0],
+            LifeStatus::Ko if self.current_player == defender => &solvers[0],
+            LifeStatus::Ko => &solvers[1],
+            LifeStatus::Unknown => return Some(LifeSolution { status, key_move: None, variation: Vec::new() }),
+        };
+        let variation = solver.variation(&self.chains, self.current_player, ko);
+        Some(LifeSolution { status, key_move: variation.first().copied(), variation })
+    }
+}
+
+impl Solver {
+    fn new(target: Point, defender: Stone, region: &[Point], exempt: Stone, budget: usize) -> Solver {
+        Solver {
+            target,
+            defender,
+            region: region.to_vec(),
+            exempt,
+            table: HashMap::new(),
+            path: Vec::new(),
+            cycled: false,
+            budget,
+            max_depth: region.len() * 4 + 4,
+        }
+    }
+
+    // Lives: Whether the target survives best play from here (None if the search limit cut the answer off)
+    fn lives(
+        &mut self,
+        chains: &Chains,
+        to_move: Stone,
+        ko: Option<Point>,
+        passed: bool,
+        depth: usize,
+    ) -> Option<bool> {
+        if chains.liberties(self.target.0, self.target.1) == 0 {
+            return Some(false);
+        }
+        let key = Key { hash: chains.hash(), to_move, ko, passed };
+        if let Some(&(lives, _)) = self.table.get(&key) {
+            return Some(lives);
+        }
+        if depth >= self.max_depth || self.budget == 0 {
+            return None;
+        }
+        self.budget -= 1;
+
+        // Moves: The mover wins with the first move that proves its result (AND/OR search with cutoffs)
+        let wants = to_move == self.defender;
+        let opponent = to_move.opponent();
+        let mut unknown = false;
+        let mut resistance = None;
+        let mut proof = None;
+        let cycled = std::mem::take(&mut self.cycled);
+        self.path.push(chains.hash());
+        for mv in self.moves(chains, to_move, ko) {
+            let result = match mv {
+                Move::Pass if passed => Some(true),
+                Move::Pass => self.lives(chains, opponent, None, true, depth + 1),
+                Move::Play((row, col)) => {
+                    let (next, ko) = play(chains, row, col, to_move);
+                    if self.repeats(next.hash(), to_move) {
+                        self.cycled = true;
+                        continue;
+                    }
+                    self.lives(&next, opponent, ko, false, depth + 1)
+                }
+            };
+            match result {
+                Some(lives) if lives == wants => {
+                    proof = Some(mv);
+                    break;
+                }
+                Some(_) => {
+                    resistance.get_or_insert(mv);
+                }
+                None => unknown = true,
+            }
+        }
+        self.path.pop();
+
+        let (lives, mv) = match proof {
+            Some(mv) => (wants, mv),
+            None if unknown => {
+                self.cycled |= cycled;
+                return None;
+            }
+            None => (!wants, resistance.unwrap_or(Move::Pass)),
+        };
+        // Table: Only results that hold however the position was reached are kept
+        if !self.cycled {
+            self.table.insert(key, (lives, mv));
+        }
+        self.cycled |= cycled;
+        Some(lives)
+    }
+
+    // Moves: Liberties of the target first, then the rest of the region's empty points; the defender tries
+    // a pass first (a group that is already alive needs no move), the attacker last
+    // Suicide is skipped, and so is the ko point for a player bound by the ko ban
+    fn moves(&self, chains: &Chains, to_move: Stone, ko: Option<Point>) -> Vec<Move> {
+        let liberties = chains.liberty_points(self.target.0, self.target.1);
+        let mut points: Vec<Point> = self
+            .region
+            .iter()
+            .copied()
+            .filter(|&(row, col)| chains.stone(row, col) == Stone::Empty)
+            .filter(|&point| to_move == self.exempt || Some(point) != ko)
+            .filter(|&(row, col)| !chains.probe(row, col, to_move).suicide)
+            .collect();
+        points.sort_by_key(|point| !liberties.contains(point));
+        let moves = points.into_iter().map(Move::Play);
+        match to_move == self.defender {
+            true => std::iter::once(Move::Pass).chain(moves).collect(),
+            false => moves.chain(std::iter::once(Move::Pass)).collect(),
+        }
+    }
+
+    // Repeats: Whether a position is already on the path - allowed only for the side exempt from the ko ban
+    // retaking a ko (back to the position before the opponent's capture, as if after a ko threat and answer)
+    fn repeats(&self, hash: u64, to_move: Stone) -> bool {
+        let retake = to_move == self.exempt && self.path.len() >= 2 && self.path[self.path.len() - 2] == hash;
+        !retake && self.path.contains(&hash)
+    }
+
+    // Variation: Follow the proven moves from the root until the fight ends
+    fn variation(&self, chains: &Chains, mut to_move: Stone, mut ko: Option<Point>) -> Vec<Move> {
+        let mut chains = chains.clone();
+        let mut passed = false;
+        let mut variation = Vec::new();
+        while chains.liberties(self.target.0, self.target.1) > 0 && variation.len() < self.max_depth {
+            let key = Key { hash: chains.hash(), to_move, ko, passed };
+            let Some(&(_, mv)) = self.table.get(&key) else { break };
+            variation.push(mv);
+            match mv {
+                Move::Pass if passed => break,
+                Move::Pass => (passed, ko) = (true, None),
+                Move::Play((row, col)) => {
+                    (chains, ko) = play(&chains, row, col, to_move);
+                    passed = false;
+                }
+            }
+            to_move = to_move.opponent();
+        }
+        variation
+    }
+}
+
+// Play: Position after stone plays at (row, col), and the ko point it leaves for the opponent
+// (a lone stone with one liberty that just captured one stone)
+fn play(chains: &Chains, row: usize, col: usize, stone: Stone) -> (Chains, Option<Point>) {
+    let mut next = chains.clone();
+    let (captured, _) = next.play(row, col, stone);
+    let ko = (captured.len() == 1 && next.chain(row, col).len() == 1 && next.liberties(row, col) == 1)
+        .then(|| captured[0]);
+    (next, ko)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Position: Board of the given size with the given Black and White stones and player to move
+    fn position(size: usize, black: &[Point], white: &[Point], to_move: Stone) -> GameState {
+        let mut stones: Vec<(usize, usize, Stone)> = black.iter().map(|&(r, c)| (r, c, Stone::Black)).collect();
+        stones.extend(white.iter().map(|&(r, c)| (r, c, Stone::White)));
+        let mut game = GameState::with_size(size);
+        game.apply_setup(&stones, to_move);
+        game
+    }
+
+    // Straight Three: Black walls off (4, 1)-(4, 3) on the edge of a 5x5 board, under a White wall on row 2
+    fn straight_three(to_move: Stone) -> GameState {
+        let black = [(3, 0), (3, 1), (3, 2), (3, 3), (3, 4), (4, 0), (4, 4)];
+        let white: Vec<Point> = (0..5).map(|col| (2, col)).collect();
+        position(5, &black, &white, to_move)
+    }
+
+    const EYE_SPACE: [Point; 3] = [(4, 1), (4, 2), (4, 3)];
+
+    #[test]
+    fn straight_three_lives_or_dies_by_who_moves_first() {
+        let solution = straight_three(Stone::Black).solve_life((3, 0), &EYE_SPACE).unwrap();
+        assert_eq!(solution.status, LifeStatus::Alive);
+        assert_eq!(solution.key_move, Some(Move::Play((4, 2))));
+
+        let solution = straight_three(Stone::White).solve_life((3, 0), &EYE_SPACE).unwrap();
+        assert_eq!(solution.status, LifeStatus::Dead);
+        assert_eq!(solution.key_move, Some(Move::Play((4, 2))));
+        assert_eq!(solution.variation.first(), solution.key_move.as_ref());
+    }
+
+    #[test]
+    fn two_eyes_are_alive_without_reading() {
+        let mut game = straight_three(Stone::White);
+        game.apply_setup(&[(4, 2, Stone::Black)], Stone::White);
+        let solution = game.solve_life((3, 0), &EYE_SPACE).unwrap();
+        assert_eq!(solution, LifeSolution { status: LifeStatus::Alive, key_move: None, variation: Vec::new() });
+        assert_eq!(game.solve_life((1, 1), &EYE_SPACE), None);
+    }
+
+    #[test]
+    fn ko_for_an_eye() {
+        // Ko: Black at (5, 0) takes the White stone at (5, 1) for a second eye, which White may retake
+        let black = [(4, 1), (4, 2), (4, 3), (4, 4), (4, 5), (5, 2), (5, 3), (5, 4)];
+        let mut white: Vec<Point> = (0..6).map(|col| (3, col)).collect();
+        white.extend([(4, 0), (5, 1)]);
+        let region: Vec<Point> = (0..6).map(|col| (5, col)).collect();
+
+        let solution = position(6, &black, &white, Stone::Black).solve_life((4, 1), &region).unwrap();
+        assert_eq!(solution.status, LifeStatus::Ko);
+        assert_eq!(solution.key_move, Some(Move::Play((5, 0))));
+
+        let solution = position(6, &black, &white, Stone::White).solve_life((4, 1), &region).unwrap();
+        assert_eq!(solution.status, LifeStatus::Dead);
+    }
+
+    #[test]
+    fn search_limit_gives_unknown() {
+        let solution = straight_three(Stone::White).solve_life_within((3, 0), &EYE_SPACE, 1).unwrap();
+        assert_eq!(solution, LifeSolution { status: LifeStatus::Unknown, key_move: None, variation: Vec::new() });
+    }
+}
@@ -0,0 +1,122 @@
+// Imports: Serialization and game types
+use serde::Serialize;
+
+use crate::{Board, GameState, Point};
+
+// Struct: Beginner mistakes a candidate move would make, for the player to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct MoveWarnings {
+    pub self_atari: bool,      // Leaves the new stone's chain with one liberty or none (ko captures excepted)
+    pub fills_own_eye: bool,   // Plays on a point surrounded only by the player's stones
+    pub ignores_atari: bool,   // Another of the player's chains stays in atari
+    pub allows_snapback: bool, // Captures, but the capturing chain (two or more stones) can be taken at once
+}
+
+impl MoveWarnings {
+    // Any: Whether the move deserves a second thought
+    pub fn any(&self) -> bool {
+        self.self_atari || self.fills_own_eye || self.ignores_atari || self.allows_snapback
+    }
+}
+
+impl GameState {
+    // Move Warnings: What playing at (row, col) would risk, worked out without playing it
+    // None if the move is not legal for the player to move
+    pub fn move_warnings(&self, row: usize, col: usize) -> Option<MoveWarnings> {
+        if !self.is_valid_move(row, col) {
+            return None;
+        }
+        let player = self.current_player;
+
+        // Atari: The player's stones whose chains are in atari before the move
+        let in_atari: Vec<Point> = (0..self.size)
+            .flat_map(|r| (0..self.size).map(move |c| (r, c)))
+            .filter(|&(r, c)| self.board[r][c] == player && self.chains.liberties(r, c) == 1)
+            .collect();
+
+        let mut next = self.chains.clone();
+        let (captured, suicided) = next.play(row, col, player);
+        let liberties = next.liberty_points(row, col);
+        let chain = next.chain(row, col);
+        let ko = captured.len() == 1 && chain.len() == 1;
+        // Suicide: Legal under some rulesets, and worse than self-atari
+        let self_atari = (liberties.len() == 1 && !ko) || !suicided.is_empty();
+        // Snapback: The opponent takes back more than one stone on the capturing chain's last liberty
+        let allows_snapback = self_atari && !captured.is_empty() && chain.len() > 1;
+        let ignores_atari =
+            in_atari.iter().any(|&(r, c)| next.liberties(r, c) == 1 && !chain.contains(&(r, c)));
+        let fills_own_eye = self.is_own_eye(row, col, player);
+
+        Some(MoveWarnings { self_atari, fills_own_eye, ignores_atari, allows_snapback })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ruleset, Stone};
+
+    // Position: A 5x5 board with the given Black and White stones, Black to move
+    fn position(ruleset: Ruleset, black: &[Point], white: &[Point]) -> GameState {
+        let mut stones: Vec<(usize, usize, Stone)> = black.iter().map(|&(r, c)| (r, c, Stone::Black)).collect();
+        stones.extend(white.iter().map(|&(r, c)| (r, c, Stone::White)));
+        let mut game = GameState::with_rules(5, ruleset);
+        game.apply_setup(&stones, Stone::Black);
+        game
+    }
+
+    #[test]
+    fn self_atari_and_suicide() {
+        let game = position(Ruleset::Japanese, &[], &[(0, 1)]);
+        let warnings = game.move_warnings(0, 0).unwrap();
+        assert_eq!(warnings, MoveWarnings { self_atari: true, ..MoveWarnings::default() });
+        assert!(!game.move_warnings(2, 2).unwrap().any());
+
+        // Suicide: Single stone where the ruleset allows it, two stones under every suicide ruleset (Ing included)
+        let game = position(Ruleset::NewZealand, &[], &[(0, 1), (1, 0)]);
+        assert!(game.move_warnings(0, 0).unwrap().self_atari);
+        let (black, white) = ([(0, 0)], [(0, 1), (1, 1), (2, 0)]);
+        assert_eq!(position(Ruleset::Japanese, &black, &white).move_warnings(1, 0), None);
+        for ruleset in [Ruleset::NewZealand, Ruleset::TrompTaylor, Ruleset::Ing] {
+            let game = position(ruleset, &black, &white);
+            assert!(game.move_warnings(1, 0).unwrap().self_atari, "{:?}", ruleset);
+        }
+    }
+
+    #[test]
+    fn taking_a_ko_is_not_self_atari() {
+        // Ko: Black at (1, 2) takes the White stone at (1, 1) and is left with that point as its only liberty
+        let game = position(Ruleset::Japanese, &[(0, 1), (1, 0), (2, 1)], &[(1, 1), (0, 2), (2, 2), (1, 3)]);
+        assert!(!game.move_warnings(1, 2).unwrap().any());
+    }
+
+    #[test]
+    fn filling_an_own_eye() {
+        let game = position(Ruleset::Japanese, &[(0, 1), (1, 0)], &[]);
+        let warnings = game.move_warnings(0, 0).unwrap();
+        assert_eq!(warnings, MoveWarnings { fills_own_eye: true, ..MoveWarnings::default() });
+    }
+
+    #[test]
+    fn ignoring_a_chain_in_atari() {
+        let game = position(Ruleset::Japanese, &[(0, 0)], &[(0, 1)]);
+        assert!(game.move_warnings(3, 3).unwrap().ignores_atari);
+        assert!(!game.move_warnings(1, 0).unwrap().any());
+    }
+
+    #[test]
+    fn capturing_into_a_snapback() {
+        // Snapback: Black at (0, 1) takes the White stone at (0, 2), and White takes both corner stones back there
+        let game = position(Ruleset::Japanese, &[(0, 0), (0, 3), (1, 2)], &[(0, 2), (1, 0), (1, 1)]);
+        let warnings = game.move_warnings(0, 1).unwrap();
+        assert!(warnings.allows_snapback);
+        assert!(warnings.self_atari);
+        assert!(!warnings.ignores_atari);
+
+        // Snapback: White does retake two stones
+        let mut game = game;
+        assert!(game.place_stone(0, 1));
+        assert!(game.place_stone(0, 2));
+        assert_eq!(game.white_captured(), 2);
+    }
+}
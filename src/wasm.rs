@@ -0,0 +1,158 @@
+// WebAssembly: JavaScript-facing methods that return JsValue, built with the "wasm" feature
+// The typed Rust API lives in the other modules; these only convert its results for JavaScript
+use wasm_bindgen::prelude::*;
+
+use serde::Serialize;
+
+use crate::{Bot, GameState, GameTree, IllegalMove, Mcts, Move, MoveOutcome, Point, Stone};
+
+#[wasm_bindgen]
+impl GameState {
+    // Play: Place a stone for the player to move; returns {player, point, captured, suicided}
+    // or throws {kind, message} (kind: Occupied, OutOfBounds, Suicide, Ko, Superko, GameOver, HandicapPending)
+    #[wasm_bindgen(js_name = try_play)]
+    pub fn try_play_js(&mut self, row: usize, col: usize) -> Result<JsValue, JsValue> {
+        move_result(self.try_play(Move::Play((row, col))))
+    }
+
+    // Pass: Pass for the player to move; same result/error objects as try_play
+    pub fn try_pass(&mut self) -> Result<JsValue, JsValue> {
+        move_result(self.try_play(Move::Pass))
+    }
+
+    // Generate: Let the bot play for the player to move; same result/error objects as try_play
+    #[wasm_bindgen(js_name = genmove)]
+    pub fn genmove_js(&mut self, bot: &mut Mcts) -> Result<JsValue, JsValue> {
+        move_result(self.genmove(bot))
+    }
+
+    // Bot Move: Let a difficulty-levelled bot play for the player to move; same result/error objects as try_play
+    pub fn play_bot(&mut self, bot: &mut Bot) -> Result<JsValue, JsValue> {
+        move_result(self.genmove(bot))
+    }
+
+    // Ladder: Read the ladder against the chain at (row, col); {works, moves: [[row, col], ...]} or null
+    #[wasm_bindgen(js_name = ladder_status)]
+    pub fn ladder_status_js(&self, row: usize, col: usize) -> Option<JsValue> {
+        self.ladder_status(row, col).and_then(|ladder| serde_wasm_bindgen::to_value(&ladder).ok())
+    }
+
+    // Solve Life: Read the fight for the chain at (row, col) within region ([[row, col], ...]);
+    // {status, key_move, variation} (status: Alive, Dead, Ko, Unknown) or null for an empty point
+    #[wasm_bindgen(js_name = solve_life)]
+    pub fn solve_life_js(&self, row: usize, col: usize, region: JsValue) -> Result<JsValue, JsValue> {
+        let region: Vec<Point> = serde_wasm_bindgen::from_value(region).map_err(JsValue::from)?;
+        Ok(self.solve_life((row, col), &region).map_or(JsValue::NULL, |s| serde_wasm_bindgen::to_value(&s).unwrap()))
+    }
+
+    // Pass-Alive: Unconditionally alive chains of player and their vital regions;
+    // {chains: [[[row, col], ...], ...], regions: [[[row, col], ...], ...]}
+    #[wasm_bindgen(js_name = pass_alive)]
+    pub fn pass_alive_js(&self, player: Stone) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.pass_alive(player)).unwrap()
+    }
+
+    // Group: The chain at (row, col); {color, stones, liberties, liberty_count, in_atari, enemies} or null
+    // (points are [row, col]; enemies holds each adjacent enemy group's first stone)
+    #[wasm_bindgen(js_name = group_at)]
+    pub fn group_at_js(&self, row: usize, col: usize) -> Option<JsValue> {
+        self.group_at(row, col).and_then(|group| serde_wasm_bindgen::to_value(&group).ok())
+    }
+
+    // All Groups: Every chain on the board as group objects, ordered by first stone
+    #[wasm_bindgen(js_name = all_groups)]
+    pub fn all_groups_js(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.all_groups()).unwrap()
+    }
+
+    // Move Warnings: Risks of playing at (row, col) for the player to move, without playing it;
+    // {self_atari, fills_own_eye, ignores_atari, allows_snapback} or null for an illegal move
+    #[wasm_bindgen(js_name = move_warnings)]
+    pub fn move_warnings_js(&self, row: usize, col: usize) -> Option<JsValue> {
+        self.move_warnings(row, col).and_then(|warnings| serde_wasm_bindgen::to_value(&warnings).ok())
+    }
+
+    // Board: Get entire board as JavaScript array (0 empty, 1 black, 2 white)
+    pub fn get_board(&self) -> JsValue {
+        let board: Vec<u8> = self.board().iter().flatten().map(Stone::code).collect();
+        serde_wasm_bindgen::to_value(&board).unwrap()
+    }
+
+    // Last Move: Get last played position as JavaScript array
+    pub fn get_last_move(&self) -> Option<JsValue> {
+        self.last_move().and_then(|point| serde_wasm_bindgen::to_value(&point).ok())
+    }
+
+    // Valid Moves: Get all legal moves as JavaScript array
+    pub fn get_valid_moves(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.valid_moves()).unwrap()
+    }
+
+    // Score: Final scores with komi once both players accept (returns [black_score, white_score] as JsValue)
+    pub fn calculate_scores(&self) -> JsValue {
+        let score = self.final_score().unwrap_or_default();
+        serde_wasm_bindgen::to_value(&[score.black, score.white]).unwrap()
+    }
+
+    // Preview: Scores with the current dead stones while still agreeing (returns [black_score, white_score])
+    pub fn preview_scores(&self) -> JsValue {
+        let score = self.score();
+        serde_wasm_bindgen::to_value(&[score.black, score.white]).unwrap()
+    }
+
+    // Board Data: Get all intersections with stone, star point, valid move, last move info
+    pub fn get_board_data(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.board_view().intersections).unwrap()
+    }
+
+    // Seki: Get the groups living in seki as JavaScript array of [row, col] lists
+    #[wasm_bindgen(js_name = seki_groups)]
+    pub fn seki_groups_js(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.seki_groups()).unwrap()
+    }
+
+    // History: Get the recorded moves as JavaScript array
+    pub fn get_move_history(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(self.moves()).unwrap()
+    }
+
+    // Import: Load an SGF record from JavaScript (throws the error message on failure)
+    #[wasm_bindgen(js_name = from_sgf)]
+    pub fn from_sgf_js(sgf: &str) -> Result<GameState, JsValue> {
+        GameState::from_sgf(sgf).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+#[wasm_bindgen]
+impl GameTree {
+    // Node: Get a node (id, parent, children, move record) as JavaScript object
+    pub fn get_node(&self, id: usize) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.node(id)).unwrap()
+    }
+
+    // Import: Load an SGF record with variations from JavaScript (throws the error message on failure)
+    #[wasm_bindgen(js_name = from_sgf)]
+    pub fn from_sgf_js(sgf: &str) -> Result<GameTree, JsValue> {
+        GameTree::from_sgf(sgf).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+// Move Result: Outcome as a JavaScript object, or the illegal-move reason as a thrown object
+fn move_result(result: Result<MoveOutcome, IllegalMove>) -> Result<JsValue, JsValue> {
+    #[derive(Serialize)]
+    struct MoveError {
+        kind: IllegalMove,
+        message: String,
+    }
+
+    match result {
+        Ok(outcome) => Ok(serde_wasm_bindgen::to_value(&outcome).unwrap()),
+        Err(kind) => Err(serde_wasm_bindgen::to_value(&MoveError { kind, message: kind.to_string() }).unwrap()),
+    }
+}
+
+// Init: Initialize WebAssembly module (set up panic hook for better error messages)
+#[wasm_bindgen]
+pub fn init() {
+    console_error_panic_hook::set_once();
+}
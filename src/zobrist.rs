@@ -0,0 +1,71 @@
+// Imports: Hashing and stone colors and the largest supported board
+use std::hash::Hasher;
+
+use crate::{Stone, MAX_BOARD_SIZE};
+
+// Constant: Number of key slots (one per point of the largest board)
+const POINTS: usize = MAX_BOARD_SIZE * MAX_BOARD_SIZE;
+
+// Keys: One random 64-bit key per (point, color), generated at compile time
+static KEYS: [[u64; 2]; POINTS] = generate_keys();
+
+// Generate: Fill the key table with a fixed-seed SplitMix64 sequence (stable across runs)
+const fn generate_keys() -> [[u64; 2]; POINTS] {
+    let mut keys = [[0u64; 2]; POINTS];
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut point = 0;
+    while point < POINTS {
+        let mut color = 0;
+        while color < 2 {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            keys[point][color] = z ^ (z >> 31);
+            color += 1;
+        }
+        point += 1;
+    }
+    keys
+}
+
+// Key: Zobrist key for a stone at (row, col); empty points contribute nothing
+pub fn key(row: usize, col: usize, stone: Stone) -> u64 {
+    let point = row * MAX_BOARD_SIZE + col;
+    match stone {
+        Stone::Black => KEYS[point][0],
+        Stone::White => KEYS[point][1],
+        Stone::Empty => 0,
+    }
+}
+
+// Hash: XOR of the keys of every stone on the board
+pub fn hash_board(board: &[Vec<Stone>]) -> u64 {
+    let mut hash = 0;
+    for (row, line) in board.iter().enumerate() {
+        for (col, &stone) in line.iter().enumerate() {
+            hash ^= key(row, col, stone);
+        }
+    }
+    hash
+}
+
+// Struct: Hasher for maps keyed by Zobrist hashes - the keys are already random, so they are used as they are
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ byte as u64;
+        }
+    }
+
+    fn write_u64(&mut self, hash: u64) {
+        self.0 ^= hash;
+    }
+}
@@ -0,0 +1,131 @@
+// Cross-Check: GameState and Bitboard play the same games move by move and must agree on everything they show
+use go_game::{Bitboard, Board, GameState, HandicapPlacement, IllegalMove, Move, Rng, Ruleset, Stone};
+
+const RULESETS: [Ruleset; 6] =
+    [Ruleset::Japanese, Ruleset::Chinese, Ruleset::Aga, Ruleset::NewZealand, Ruleset::TrompTaylor, Ruleset::Ing];
+
+// Agree: Stones, player to move, prisoners, game end, and why each point is or is not playable
+fn assert_same(game: &GameState, bitboard: &Bitboard, seed: u64) {
+    let size = game.size();
+    assert_eq!(bitboard.size(), size, "sizes differ (seed {})", seed);
+    for row in 0..size {
+        for col in 0..size {
+            assert_eq!(Board::stone(game, row, col), bitboard.stone(row, col), "stones differ (seed {})", seed);
+            assert_eq!(
+                Board::check_move(game, row, col),
+                bitboard.check_move(row, col),
+                "legality of ({}, {}) differs (seed {})",
+                row,
+                col,
+                seed
+            );
+        }
+    }
+    assert_eq!(game.empty_points(), bitboard.empty_points(), "empty points differ (seed {})", seed);
+    assert_eq!(game.legal_moves(), bitboard.legal_moves(), "legal moves differ (seed {})", seed);
+    assert_eq!(Board::to_move(game), bitboard.to_move(), "player to move differs (seed {})", seed);
+    for player in [Stone::Black, Stone::White] {
+        assert_eq!(game.prisoners(player), bitboard.prisoners(player), "prisoners differ (seed {})", seed);
+    }
+    assert_eq!(game.game_over(), bitboard.game_over(), "game end differs (seed {})", seed);
+}
+
+// Lockstep: Play the same random moves on both boards, checking they agree after every move
+fn lockstep(game: &mut GameState, bitboard: &mut Bitboard, seed: u64, moves: usize) {
+    let mut rng = Rng::new(seed);
+    assert_same(game, bitboard, seed);
+    for _ in 0..moves {
+        if game.game_over() {
+            break;
+        }
+        let mv = game.random_move(&mut rng);
+        assert_eq!(Board::play(game, mv), bitboard.play(mv), "outcomes differ (seed {})", seed);
+        assert_same(game, bitboard, seed);
+    }
+}
+
+// Random Game: A game on a fresh board, advanced the given number of random moves (fewer if it ends)
+fn random_game(size: usize, ruleset: Ruleset, seed: u64, moves: usize) -> GameState {
+    let mut game = GameState::with_rules(size, ruleset);
+    let mut rng = Rng::new(seed);
+    for _ in 0..moves {
+        if game.game_over() {
+            break;
+        }
+        let mv = game.random_move(&mut rng);
+        game.try_play(mv).unwrap();
+    }
+    game
+}
+
+#[test]
+fn random_games_agree_under_every_ruleset() {
+    for seed in 1..=36 {
+        let ruleset = RULESETS[seed as usize % RULESETS.len()];
+        let size = [5, 7, 9, 13][seed as usize % 4];
+        let mut game = GameState::with_rules(size, ruleset);
+        let mut bitboard = Bitboard::new(size, ruleset);
+        lockstep(&mut game, &mut bitboard, seed, size * size * 3);
+    }
+}
+
+#[test]
+fn large_boards_agree() {
+    for (seed, size) in [(101, 19), (102, 21), (103, 25)] {
+        for ruleset in [Ruleset::Chinese, Ruleset::TrompTaylor] {
+            let mut game = GameState::with_rules(size, ruleset);
+            let mut bitboard = Bitboard::new(size, ruleset);
+            lockstep(&mut game, &mut bitboard, seed, 150);
+        }
+    }
+}
+
+#[test]
+fn from_game_copies_positions_with_captures() {
+    for (seed, &ruleset) in (201..).zip(RULESETS.iter()) {
+        let mut game = random_game(9, ruleset, seed, 80);
+        assert!(!game.game_over(), "game over before the copy (seed {})", seed);
+        assert!(game.black_captured() + game.white_captured() > 0, "no captures to copy (seed {})", seed);
+        let mut bitboard = Bitboard::from_game(&game);
+        lockstep(&mut game, &mut bitboard, seed, 200);
+    }
+}
+
+#[test]
+fn from_game_keeps_the_ko_ban() {
+    for ruleset in RULESETS {
+        // Ko: White's stone at (2, 2) is taken from (2, 3), and White may not take back at once
+        let mut game = GameState::with_rules(9, ruleset);
+        for point in [(1, 2), (1, 3), (2, 1), (2, 4), (3, 2), (3, 3), (7, 7), (2, 2), (2, 3)] {
+            game.try_play(Move::Play(point)).unwrap();
+        }
+        assert_eq!(game.check_move(2, 2), Err(IllegalMove::Ko));
+
+        let mut bitboard = Bitboard::from_game(&game);
+        assert_eq!(bitboard.check_move(2, 2), Err(IllegalMove::Ko), "{:?}", ruleset);
+        lockstep(&mut game, &mut bitboard, 300, 100);
+    }
+}
+
+#[test]
+fn from_game_after_handicap() {
+    for (seed, size) in [(401, 9), (402, 13), (403, 19)] {
+        for ruleset in [Ruleset::Japanese, Ruleset::Aga] {
+            let mut game = GameState::with_rules(size, ruleset);
+            assert!(game.new_handicap(4, HandicapPlacement::Fixed));
+            let mut bitboard = Bitboard::from_game(&game);
+            assert_eq!(bitboard.to_move(), Stone::White);
+            lockstep(&mut game, &mut bitboard, seed, 100);
+        }
+    }
+
+    // Free: Black places the stones, then the copy starts with White to move
+    let mut game = GameState::with_rules(9, Ruleset::Chinese);
+    assert!(game.new_handicap(3, HandicapPlacement::Free));
+    for (row, col) in [(2, 2), (6, 6), (4, 4)] {
+        assert!(game.place_stone(row, col));
+    }
+    assert_eq!(game.handicap_to_place(), 0);
+    let mut bitboard = Bitboard::from_game(&game);
+    lockstep(&mut game, &mut bitboard, 404, 100);
+}